@@ -0,0 +1,257 @@
+use crate::factory::bean_error::BeanError;
+use crate::factory::bean_factory::{DefaultListableBeanFactory, NamedBean};
+use crate::factory::bean_wrapper::BeanWrapper;
+use crate::factory::condition::Condition;
+use crate::factory::initializing_bean::InitializingBean;
+use crate::factory::scope::Scope;
+use std::any::{Any, TypeId};
+use std::error::Error;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Instant;
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+pub type BeanCreationResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+type BeanSupplier<T> = Arc<
+    dyn for<'a> Fn(&'a DefaultListableBeanFactory) -> BoxFuture<'a, BeanCreationResult<T>>
+        + Send
+        + Sync,
+>;
+
+/// Registers one of the instances of a created bean as a singleton.
+type BeanPublisher =
+    Arc<dyn Fn(&DefaultListableBeanFactory, &BeanWrapper) -> Result<(), BeanError> + Send + Sync>;
+
+/// A dependency on the bean of a type, resolved to the definition providing it.
+pub(crate) struct TypeDependency {
+    pub(crate) type_id: TypeId,
+    /// Whether a bean of the type is registered already.
+    pub(crate) registered: fn(&DefaultListableBeanFactory) -> bool,
+}
+
+type InterfaceView<T> = Arc<dyn Fn(Arc<T>) -> Box<dyn Any + Send + Sync> + Send + Sync>;
+
+/// Describes how to create a bean: a factory function plus the beans it depends on.
+///
+/// Non-lazy definitions are instantiated in dependency order when the context is
+/// refreshed, lazy ones on their first lookup.
+pub struct BeanDefinition<T> {
+    name: String,
+    depends_on: Vec<String>,
+    type_dependencies: Vec<TypeDependency>,
+    lazy: bool,
+    scope: Scope,
+    supplier: BeanSupplier<T>,
+    types: Vec<TypeId>,
+    publishers: Vec<BeanPublisher>,
+    views: Vec<InterfaceView<T>>,
+    conditions: Vec<Box<dyn Condition>>,
+}
+
+impl<T: Send + Sync + 'static> BeanDefinition<T> {
+    pub fn new<F>(name: &str, factory: F) -> Self
+    where
+        F: Fn(&DefaultListableBeanFactory) -> BeanCreationResult<T> + Send + Sync + 'static,
+    {
+        Self::new_async(name, move |bean_factory| {
+            let bean = factory(bean_factory);
+            Box::pin(async move { bean })
+        })
+    }
+
+    pub fn new_async<F>(name: &str, factory: F) -> Self
+    where
+        F: for<'a> Fn(&'a DefaultListableBeanFactory) -> BoxFuture<'a, BeanCreationResult<T>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        BeanDefinition {
+            name: name.to_string(),
+            depends_on: vec![],
+            type_dependencies: vec![],
+            lazy: false,
+            scope: Scope::Singleton,
+            supplier: Arc::new(factory),
+            types: vec![TypeId::of::<Arc<T>>()],
+            publishers: vec![publisher::<T>()],
+            views: vec![],
+            conditions: vec![],
+        }
+    }
+
+    /// Declare a bean that has to be created before this one.
+    pub fn depends_on(mut self, name: &str) -> Self {
+        self.depends_on.push(name.to_string());
+        self
+    }
+
+    /// Declare that the bean of type `I` has to be created before this one, if a bean
+    /// definition provides it.
+    pub fn depends_on_type<I: ?Sized + Send + Sync + 'static>(mut self) -> Self {
+        self.type_dependencies.push(TypeDependency {
+            type_id: TypeId::of::<Arc<I>>(),
+            registered: DefaultListableBeanFactory::contains_local_bean_of_type::<I>,
+        });
+        self
+    }
+
+    /// Create the bean on its first lookup instead of on refresh.
+    pub fn lazy(mut self) -> Self {
+        self.lazy = true;
+        self
+    }
+
+    /// Only register the bean if `condition` matches when the context is refreshed.
+    pub fn conditional_on(mut self, condition: impl Condition + 'static) -> Self {
+        self.conditions.push(Box::new(condition));
+        self
+    }
+
+    /// Prototype and request scoped beans are created on lookup and are only
+    /// resolved by name or with `get_all`.
+    pub fn scope(mut self, scope: Scope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Also register the bean as the trait-object type `I`, e.g.
+    /// `with_interface::<dyn ServiceRegistry>(|bean| bean)`.
+    pub fn with_interface<I: ?Sized + Send + Sync + 'static>(
+        mut self,
+        cast: fn(Arc<T>) -> Arc<I>,
+    ) -> Self {
+        self.types.push(TypeId::of::<Arc<I>>());
+        self.publishers.push(publisher::<I>());
+        self.views.push(Arc::new(move |bean| Box::new(cast(bean))));
+        self
+    }
+
+    /// Create the bean with its interface views and run the post processors and its init callback.
+    async fn build(
+        &self,
+        bean_factory: &DefaultListableBeanFactory,
+    ) -> Result<BeanWrapper, BeanError> {
+        let creation_failed = |e: Box<dyn Error + Send + Sync>| BeanError::CreationFailed {
+            name: self.name.clone(),
+            message: e.to_string(),
+        };
+        bean_factory.apply_before_instantiation(&self.name)?;
+        let started = Instant::now();
+        let bean = Arc::new(
+            (self.supplier)(bean_factory)
+                .await
+                .map_err(creation_failed)?,
+        );
+        let mut wrapper = BeanWrapper::new(&self.name, bean.clone());
+        wrapper.set_instantiation_time(started.elapsed());
+        for view in &self.views {
+            wrapper.add(view(bean.clone()));
+        }
+        bean_factory.apply_before_init(&mut wrapper)?;
+        if let Some(initializing_bean) = wrapper.get::<dyn InitializingBean>() {
+            initializing_bean
+                .after_properties_set()
+                .await
+                .map_err(creation_failed)?;
+        }
+        bean_factory.apply_after_init(&mut wrapper)?;
+        Ok(wrapper)
+    }
+}
+
+fn publisher<I: ?Sized + Send + Sync + 'static>() -> BeanPublisher {
+    Arc::new(|bean_factory, bean| match bean.get::<I>() {
+        Some(instance) => bean_factory.register::<I>(bean.get_name(), instance),
+        None => Ok(()),
+    })
+}
+
+/// Type-erased view of a [`BeanDefinition`] kept by the bean factory.
+pub(crate) trait RegisteredBeanDefinition: Send + Sync {
+    fn get_name(&self) -> &str;
+
+    fn get_depends_on(&self) -> &[String];
+
+    fn get_type_dependencies(&self) -> &[TypeDependency];
+
+    fn is_lazy(&self) -> bool;
+
+    fn get_scope(&self) -> Scope;
+
+    fn get_conditions(&self) -> &[Box<dyn Condition>];
+
+    fn provides(&self, type_id: TypeId) -> bool;
+
+    /// Create the bean and register it as a singleton.
+    fn create<'a>(
+        &'a self,
+        bean_factory: &'a DefaultListableBeanFactory,
+    ) -> BoxFuture<'a, Result<(), BeanError>>;
+
+    /// Create a new instance of the bean without registering it.
+    fn instantiate<'a>(
+        &'a self,
+        bean_factory: &'a DefaultListableBeanFactory,
+    ) -> BoxFuture<'a, Result<NamedBean, BeanError>>;
+}
+
+impl<T: Send + Sync + 'static> RegisteredBeanDefinition for BeanDefinition<T> {
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_depends_on(&self) -> &[String] {
+        &self.depends_on
+    }
+
+    fn get_type_dependencies(&self) -> &[TypeDependency] {
+        &self.type_dependencies
+    }
+
+    fn is_lazy(&self) -> bool {
+        self.lazy
+    }
+
+    fn get_scope(&self) -> Scope {
+        self.scope
+    }
+
+    fn get_conditions(&self) -> &[Box<dyn Condition>] {
+        &self.conditions
+    }
+
+    fn provides(&self, type_id: TypeId) -> bool {
+        self.types.contains(&type_id)
+    }
+
+    fn create<'a>(
+        &'a self,
+        bean_factory: &'a DefaultListableBeanFactory,
+    ) -> BoxFuture<'a, Result<(), BeanError>> {
+        Box::pin(async move {
+            let bean = self.build(bean_factory).await?;
+            for publisher in &self.publishers {
+                publisher(bean_factory, &bean)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn instantiate<'a>(
+        &'a self,
+        bean_factory: &'a DefaultListableBeanFactory,
+    ) -> BoxFuture<'a, Result<NamedBean, BeanError>> {
+        Box::pin(async move {
+            let bean = self.build(bean_factory).await?;
+            Ok(NamedBean {
+                name: self.name.clone(),
+                instances: bean.into_instances(),
+                addresses: vec![],
+            })
+        })
+    }
+}
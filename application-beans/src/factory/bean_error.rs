@@ -0,0 +1,63 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Errors raised by the bean factory when registering or resolving beans.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BeanError {
+    /// A bean with the same name is already registered.
+    DuplicateBeanName { name: String },
+    /// A bean of the same type is already registered through the type-keyed API.
+    DuplicateBeanType { type_name: &'static str },
+    /// Another instance is already registered under the name.
+    DifferentInstance { name: String },
+    /// No bean or bean definition is registered under the name.
+    NoSuchBean { name: String },
+    /// The factory function of a bean definition failed.
+    CreationFailed { name: String, message: String },
+    /// Bean definitions depend on each other, `cycle` starts and ends with the same bean.
+    CircularDependency { cycle: Vec<String> },
+    /// The destroy callback of a bean failed.
+    DestructionFailed { name: String, message: String },
+    /// A request scoped bean was looked up outside of a request.
+    ScopeNotActive { name: String },
+    /// The bean factory already has a parent.
+    ParentAlreadySet,
+}
+
+impl Display for BeanError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BeanError::DuplicateBeanName { name } => {
+                write!(f, "A bean named '{}' is already registered", name)
+            }
+            BeanError::DuplicateBeanType { type_name } => write!(
+                f,
+                "A bean of type '{}' is already registered, use set_named to register another instance",
+                type_name
+            ),
+            BeanError::DifferentInstance { name } => write!(
+                f,
+                "Another instance is already registered as bean '{}', a bean can only add traits of the same instance",
+                name
+            ),
+            BeanError::NoSuchBean { name } => write!(f, "No bean named '{}' is registered", name),
+            BeanError::CreationFailed { name, message } => {
+                write!(f, "Error creating bean '{}': {}", name, message)
+            }
+            BeanError::CircularDependency { cycle } => {
+                write!(f, "Circular dependency between beans: {}", cycle.join(" -> "))
+            }
+            BeanError::DestructionFailed { name, message } => {
+                write!(f, "Error destroying bean '{}': {}", name, message)
+            }
+            BeanError::ScopeNotActive { name } => write!(
+                f,
+                "Bean '{}' is request scoped but no request is being handled",
+                name
+            ),
+            BeanError::ParentAlreadySet => write!(f, "The parent bean factory is already set"),
+        }
+    }
+}
+
+impl Error for BeanError {}
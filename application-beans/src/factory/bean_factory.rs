@@ -1,42 +1,1285 @@
+use crate::factory::bean_definition::{BeanDefinition, BoxFuture, RegisteredBeanDefinition};
+use crate::factory::bean_error::BeanError;
+use crate::factory::bean_post_processor::BeanPostProcessor;
+use crate::factory::bean_wrapper::BeanWrapper;
+use crate::factory::condition::{matches_all, ConditionContext};
+use crate::factory::disposable_bean::DisposableBean;
+use crate::factory::initializing_bean::InitializingBean;
+use crate::factory::scope::{RequestScope, Scope};
+use application_core::env::environment::ApplicationEnvironment;
+use async_std::task::block_on;
 use state::TypeMap;
+use std::any::{type_name, Any, TypeId};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use tracing::error;
 
+tokio::task_local! {
+    /// Names of the beans being created by the current task, in creation order.
+    static CREATION_PATH: Vec<String>;
+}
+
+/// Beans are resolved either by their concrete type or by a trait-object type they
+/// were registered under with [`ConfigurableBeanFactory::set_as`], e.g. `dyn ServiceRegistry`.
+/// Prototype and request scoped beans are only resolved by `get_bean`, `get_named` and `get_all`.
+/// Lookups fall through to the parent bean factory if there is one. A bean that fails to be
+/// created is logged and not returned.
+///
+/// The lookups are synchronous, a bean definition not instantiated yet is created by blocking
+/// the thread on its factory. An async factory waiting on the runtime, e.g. on a timer or a
+/// socket, then holds a worker of a multi-threaded runtime and may never complete on a
+/// current-thread runtime. From async code, look such beans up with the `_async` getters of
+/// [`DefaultListableBeanFactory`] or have them instantiated at startup.
 pub trait BeanFactory {
-    fn get<T: 'static>(&self) -> &T;
-    fn try_get<T: 'static>(&self) -> Option<&T>;
+    /// Get the bean of type `T`, panics if there is none, see [`BeanFactory::try_get`].
+    /// Prototype and request scoped beans are not kept, they can not be borrowed.
+    fn get<T: ?Sized + 'static>(&self) -> &T;
+    fn try_get<T: ?Sized + 'static>(&self) -> Option<&T>;
+
+    /// Get the bean of type `T` as a shared reference, `None` if there is none or it cannot be created.
+    fn get_bean<T: ?Sized + Send + Sync + 'static>(&self) -> Option<Arc<T>>;
+
+    /// Get the bean registered under `name`, if it exists and is of type `T`.
+    fn get_named<T: ?Sized + Send + Sync + 'static>(&self, name: &str) -> Option<Arc<T>>;
+
+    /// Get every bean of type `T`, in registration order, followed by the beans of the parent.
+    fn get_all<T: ?Sized + Send + Sync + 'static>(&self) -> Vec<Arc<T>>;
+
+    /// Whether a bean or a bean definition is registered under `name`.
+    fn contains_bean(&self, name: &str) -> bool;
 }
 
 pub trait ListableBeanFactory: BeanFactory {
-
+    /// The number of beans, counting definitions that are not instantiated yet.
     fn get_bean_definition_count(&self) -> usize;
+
+    /// The names of the beans of this factory, not including the parent's.
+    fn get_bean_names(&self) -> Vec<String>;
+
+    /// Whether a bean of type `T` is registered or defined, without creating it.
+    fn contains_bean_of_type<T: ?Sized + Send + Sync + 'static>(&self) -> bool;
 }
 
 pub trait ConfigurableBeanFactory {
-    fn set<T: Send + Sync + 'static>(&self, state: T) -> bool;
+    /// Register the bean as the instance of type `T`, named after its type.
+    fn set<T: Send + Sync + 'static>(&self, state: T) -> Result<(), BeanError>;
+
+    /// Register the bean under `name`. The first bean of a type also becomes
+    /// the instance returned by [`BeanFactory::get`] for that type.
+    fn set_named<T: Send + Sync + 'static>(&self, name: &str, state: T) -> Result<(), BeanError>;
+
+    /// Register `bean` under `name` as the trait-object type `I`, e.g.
+    /// `set_as::<dyn ServiceRegistry>("serviceRegistry", Arc::new(registry))`.
+    /// Calling it with the name of a bean registered already, with any of the `set` methods,
+    /// adds the trait to the bean, `bean` has to be the same instance, e.g. from `get_named`.
+    /// Registering it as `dyn InitializingBean` runs its init callback, as `dyn DisposableBean`
+    /// has it destroyed when the context is closed.
+    fn set_as<I: ?Sized + Send + Sync + 'static>(
+        &self,
+        name: &str,
+        bean: Arc<I>,
+    ) -> Result<(), BeanError>;
+
+    fn register_bean_definition<T: Send + Sync + 'static>(
+        &self,
+        definition: BeanDefinition<T>,
+    ) -> Result<(), BeanError>;
+
+    /// Add a post processor for the beans registered or created from now on.
+    fn add_bean_post_processor(&self, post_processor: Arc<dyn BeanPostProcessor>);
+}
+
+/// Owner of the bean factory a child factory falls back to, usually the parent application context.
+pub trait ParentBeanFactory: Send + Sync {
+    fn get_bean_factory(&self) -> &DefaultListableBeanFactory;
+}
+
+pub(crate) struct NamedBean {
+    pub(crate) name: String,
+    /// The `Arc<T>` of the concrete type and of every trait-object type the bean is
+    /// registered under.
+    pub(crate) instances: Vec<Box<dyn Any + Send + Sync>>,
+    /// The addresses of the instance registered and of the one given to `set_as` before the
+    /// post processors ran, to tell if a trait added to the bean is implemented by the same
+    /// instance.
+    pub(crate) addresses: Vec<usize>,
+}
+
+impl NamedBean {
+    pub(crate) fn get<T: ?Sized + Send + Sync + 'static>(&self) -> Option<&Arc<T>> {
+        self.instances
+            .iter()
+            .find_map(|instance| instance.downcast_ref::<Arc<T>>())
+    }
 }
 
 #[derive(Default)]
 pub struct DefaultListableBeanFactory {
     beans: TypeMap![Send + Sync],
+    named_beans: RwLock<Vec<NamedBean>>,
+    definitions: RwLock<Vec<Arc<dyn RegisteredBeanDefinition>>>,
+    post_processors: RwLock<Vec<Arc<dyn BeanPostProcessor>>>,
+    /// Held while a bean is created, a concurrent lookup waits for the bean instead of
+    /// creating it again.
+    creation_locks: Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
+    parent: OnceLock<Arc<dyn ParentBeanFactory>>,
+}
+
+impl DefaultListableBeanFactory {
+    /// Set the factory that lookups fall through to, it can only be set once.
+    pub fn set_parent_bean_factory(
+        &self,
+        parent: Arc<dyn ParentBeanFactory>,
+    ) -> Result<(), BeanError> {
+        self.parent
+            .set(parent)
+            .map_err(|_| BeanError::ParentAlreadySet)
+    }
+
+    pub fn get_parent_bean_factory(&self) -> Option<&DefaultListableBeanFactory> {
+        self.parent.get().map(|parent| parent.get_bean_factory())
+    }
+
+    /// Whether a bean or a bean definition is registered under `name`, ignoring the parent.
+    pub fn contains_local_bean(&self, name: &str) -> bool {
+        self.contains_singleton(name) || self.get_definition(name).is_some()
+    }
+
+    /// Like [`BeanFactory::get_bean`], a pending singleton is created by awaiting its factory
+    /// instead of blocking the thread on it.
+    pub async fn get_bean_async<T: ?Sized + Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        let type_id = TypeId::of::<Arc<T>>();
+        let mut bean_factory = Some(self);
+        while let Some(current) = bean_factory {
+            if !current.contains_local_bean_of_type::<T>() {
+                if let Err(e) = current.create_singletons_of_type(type_id, false).await {
+                    error!("{}", e);
+                }
+            }
+            // the factory resolving the bean, prototype and request scoped beans included
+            if current.contains_local_bean_of_type::<T>()
+                || current.has_scoped_definition_of(type_id)
+            {
+                break;
+            }
+            bean_factory = current.get_parent_bean_factory();
+        }
+        self.get_bean::<T>()
+    }
+
+    /// Like [`BeanFactory::get_named`], a pending singleton is created by awaiting its factory
+    /// instead of blocking the thread on it.
+    pub async fn get_named_async<T: ?Sized + Send + Sync + 'static>(
+        &self,
+        name: &str,
+    ) -> Option<Arc<T>> {
+        let mut bean_factory = Some(self);
+        while let Some(current) = bean_factory {
+            if let Some(definition) = current.get_definition(name) {
+                if definition.get_scope() == Scope::Singleton {
+                    if let Err(e) = current.create_bean(definition).await {
+                        error!("{}", e);
+                        return None;
+                    }
+                }
+                break;
+            }
+            if current.contains_singleton(name) {
+                break;
+            }
+            bean_factory = current.get_parent_bean_factory();
+        }
+        self.get_named::<T>(name)
+    }
+
+    /// Like [`BeanFactory::get_all`], the pending singletons are created by awaiting their
+    /// factories instead of blocking the thread on them.
+    pub async fn get_all_async<T: ?Sized + Send + Sync + 'static>(&self) -> Vec<Arc<T>> {
+        let mut bean_factory = Some(self);
+        while let Some(current) = bean_factory {
+            let type_id = TypeId::of::<Arc<T>>();
+            if let Err(e) = current.create_singletons_of_type(type_id, true).await {
+                error!("{}", e);
+            }
+            bean_factory = current.get_parent_bean_factory();
+        }
+        self.get_all::<T>()
+    }
+
+    /// Instantiate every non-lazy bean definition, dependencies first.
+    pub async fn preinstantiate_singletons(&self) -> Result<(), BeanError> {
+        let definitions = self.sort_definitions()?;
+        for definition in definitions {
+            if definition.get_scope() != Scope::Singleton {
+                continue;
+            }
+            self.create_bean(definition).await?;
+        }
+        Ok(())
+    }
+
+    /// Remove the bean definitions whose conditions do not match, in registration order.
+    pub fn evaluate_conditions(&self, environment: &ApplicationEnvironment) {
+        let definitions = self.definitions.read().unwrap().clone();
+        let context = ConditionContext {
+            environment,
+            bean_factory: self,
+        };
+        for definition in definitions {
+            let name = definition.get_name();
+            if definition.get_conditions().is_empty() || self.contains_singleton(name) {
+                continue;
+            }
+            if !matches_all(name, definition.get_conditions(), &context) {
+                let mut definitions = self.definitions.write().unwrap();
+                definitions.retain(|definition| definition.get_name() != name);
+            }
+        }
+    }
+
+    /// Destroy the singletons registered as [`DisposableBean`], in reverse registration order.
+    /// Every bean is destroyed even if one fails, the first failure is returned.
+    pub async fn destroy_singletons(&self) -> Result<(), BeanError> {
+        let disposable_beans: Vec<(String, Arc<dyn DisposableBean>)> = {
+            let named_beans = self.named_beans.read().unwrap();
+            named_beans
+                .iter()
+                .rev()
+                .filter_map(|bean| {
+                    bean.get::<dyn DisposableBean>()
+                        .map(|disposable_bean| (bean.name.clone(), disposable_bean.clone()))
+                })
+                .collect()
+        };
+        let mut result = Ok(());
+        for (name, disposable_bean) in disposable_beans {
+            if let Err(e) = disposable_bean.destroy().await {
+                if result.is_ok() {
+                    result = Err(BeanError::DestructionFailed {
+                        name,
+                        message: e.to_string(),
+                    });
+                }
+            }
+        }
+        result
+    }
+
+    fn sort_definitions(&self) -> Result<Vec<Arc<dyn RegisteredBeanDefinition>>, BeanError> {
+        let definitions = self.definitions.read().unwrap().clone();
+        let mut sorted = Vec::new();
+        let mut visited = HashSet::new();
+        let mut path = Vec::new();
+        for definition in definitions
+            .iter()
+            .filter(|definition| !definition.is_lazy())
+        {
+            self.visit_definition(
+                definition,
+                &definitions,
+                &mut visited,
+                &mut path,
+                &mut sorted,
+            )?;
+        }
+        Ok(sorted)
+    }
+
+    fn visit_definition(
+        &self,
+        definition: &Arc<dyn RegisteredBeanDefinition>,
+        definitions: &[Arc<dyn RegisteredBeanDefinition>],
+        visited: &mut HashSet<String>,
+        path: &mut Vec<String>,
+        sorted: &mut Vec<Arc<dyn RegisteredBeanDefinition>>,
+    ) -> Result<(), BeanError> {
+        let name = definition.get_name();
+        if visited.contains(name) {
+            return Ok(());
+        }
+        if let Some(index) = path.iter().position(|bean_name| bean_name == name) {
+            let mut cycle = path[index..].to_vec();
+            cycle.push(name.to_string());
+            return Err(BeanError::CircularDependency { cycle });
+        }
+        path.push(name.to_string());
+        for dependency in &self.get_dependencies(definition.as_ref(), definitions) {
+            let dependency_definition = definitions
+                .iter()
+                .find(|definition| definition.get_name() == dependency);
+            match dependency_definition {
+                Some(dependency_definition) => self.visit_definition(
+                    dependency_definition,
+                    definitions,
+                    visited,
+                    path,
+                    sorted,
+                )?,
+                None if self.contains_singleton(dependency) => {}
+                None => {
+                    return Err(BeanError::NoSuchBean {
+                        name: dependency.clone(),
+                    })
+                }
+            }
+        }
+        path.pop();
+        visited.insert(name.to_string());
+        sorted.push(definition.clone());
+        Ok(())
+    }
+
+    fn create_bean(
+        &self,
+        definition: Arc<dyn RegisteredBeanDefinition>,
+    ) -> BoxFuture<'_, Result<(), BeanError>> {
+        Box::pin(async move {
+            let name = definition.get_name().to_string();
+            if self.contains_singleton(&name) {
+                return Ok(());
+            }
+            let path = get_creation_path(&name)?;
+            // a cycle of dependencies created by two tasks would wait on each other
+            let definitions = self.definitions.read().unwrap().clone();
+            self.visit_definition(
+                &definition,
+                &definitions,
+                &mut HashSet::new(),
+                &mut vec![],
+                &mut vec![],
+            )?;
+            let lock = self
+                .creation_locks
+                .lock()
+                .unwrap()
+                .entry(name.clone())
+                .or_default()
+                .clone();
+            let _guard = lock.lock().await;
+            if self.contains_singleton(&name) {
+                return Ok(());
+            }
+            CREATION_PATH
+                .scope(path, async {
+                    self.create_dependencies(definition.as_ref()).await?;
+                    definition.create(self).await
+                })
+                .await
+        })
+    }
+
+    /// The names of the beans `definition` depends on, those of its type dependencies are
+    /// the first definitions of `definitions` providing them, unless a bean of the type is
+    /// registered already.
+    fn get_dependencies(
+        &self,
+        definition: &dyn RegisteredBeanDefinition,
+        definitions: &[Arc<dyn RegisteredBeanDefinition>],
+    ) -> Vec<String> {
+        let mut dependencies = definition.get_depends_on().to_vec();
+        for type_dependency in definition.get_type_dependencies() {
+            if (type_dependency.registered)(self) {
+                continue;
+            }
+            let provider = definitions.iter().find(|provider| {
+                provider.get_name() != definition.get_name()
+                    && provider.provides(type_dependency.type_id)
+            });
+            if let Some(provider) = provider {
+                if !dependencies.iter().any(|name| name == provider.get_name()) {
+                    dependencies.push(provider.get_name().to_string());
+                }
+            }
+        }
+        dependencies
+    }
+
+    /// Create the singletons `definition` depends on.
+    async fn create_dependencies(
+        &self,
+        definition: &dyn RegisteredBeanDefinition,
+    ) -> Result<(), BeanError> {
+        let definitions = self.definitions.read().unwrap().clone();
+        for dependency in &self.get_dependencies(definition, &definitions) {
+            if self.contains_singleton(dependency) {
+                continue;
+            }
+            match self.get_definition(dependency) {
+                Some(dependency_definition) => {
+                    if dependency_definition.get_scope() == Scope::Singleton {
+                        self.create_bean(dependency_definition).await?
+                    }
+                }
+                None => {
+                    return Err(BeanError::NoSuchBean {
+                        name: dependency.clone(),
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the prototype or request scoped bean of `definition` as `T`.
+    fn get_scoped<T: ?Sized + Send + Sync + 'static>(
+        &self,
+        definition: &dyn RegisteredBeanDefinition,
+    ) -> Result<Option<Arc<T>>, BeanError> {
+        let instantiate = || {
+            let path = get_creation_path(definition.get_name())?;
+            block_on(CREATION_PATH.scope(path, async {
+                self.create_dependencies(definition).await?;
+                definition.instantiate(self).await
+            }))
+        };
+        match definition.get_scope() {
+            Scope::Request => match RequestScope::current() {
+                Some(request_scope) => {
+                    request_scope.get_or_create(definition.get_name(), instantiate)
+                }
+                None => Err(BeanError::ScopeNotActive {
+                    name: definition.get_name().to_string(),
+                }),
+            },
+            _ => Ok(instantiate()?.get::<T>().cloned()),
+        }
+    }
+
+    /// Create the pending definitions providing `type_id`, blocking until they are created.
+    fn create_beans_of_type(&self, type_id: TypeId, all: bool) -> Result<(), BeanError> {
+        block_on(self.create_singletons_of_type(type_id, all))
+    }
+
+    /// Create the pending definitions providing `type_id`, only the first one unless `all`.
+    /// Every definition is created even if one fails, the first failure is returned.
+    async fn create_singletons_of_type(&self, type_id: TypeId, all: bool) -> Result<(), BeanError> {
+        let definitions: Vec<_> = self
+            .definitions
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|definition| definition.provides(type_id))
+            .filter(|definition| definition.get_scope() == Scope::Singleton)
+            .filter(|definition| !self.contains_singleton(definition.get_name()))
+            .cloned()
+            .collect();
+        let mut result = Ok(());
+        for definition in definitions {
+            let created = self.create_bean(definition).await;
+            if !all {
+                return created;
+            }
+            if let (Err(e), Ok(())) = (created, &result) {
+                result = Err(e);
+            }
+        }
+        result
+    }
+
+    /// Get the bean of type `T` from this factory only.
+    fn get_local_bean<T: ?Sized + Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        if self.beans.try_get::<Arc<T>>().is_none() {
+            if let Err(e) = self.create_beans_of_type(TypeId::of::<Arc<T>>(), false) {
+                error!("{}", e);
+                return None;
+            }
+        }
+        if let Some(bean) = self.beans.try_get::<Arc<T>>() {
+            return Some(bean.clone());
+        }
+        // prototype and request scoped beans are not kept by type
+        let definition = self
+            .definitions
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|definition| definition.get_scope() != Scope::Singleton)
+            .find(|definition| definition.provides(TypeId::of::<Arc<T>>()))
+            .cloned()?;
+        self.get_scoped::<T>(definition.as_ref())
+            .unwrap_or_else(|e| {
+                error!("{}", e);
+                None
+            })
+    }
+
+    fn has_scoped_definition_of(&self, type_id: TypeId) -> bool {
+        let definitions = self.definitions.read().unwrap();
+        definitions.iter().any(|definition| {
+            definition.get_scope() != Scope::Singleton && definition.provides(type_id)
+        })
+    }
+
+    fn get_definition(&self, name: &str) -> Option<Arc<dyn RegisteredBeanDefinition>> {
+        let definitions = self.definitions.read().unwrap();
+        definitions
+            .iter()
+            .find(|definition| definition.get_name() == name)
+            .cloned()
+    }
+
+    pub(crate) fn contains_local_bean_of_type<T: ?Sized + Send + Sync + 'static>(&self) -> bool {
+        self.beans.try_get::<Arc<T>>().is_some()
+    }
+
+    fn contains_singleton(&self, name: &str) -> bool {
+        let named_beans = self.named_beans.read().unwrap();
+        named_beans.iter().any(|bean| bean.name == name)
+    }
+
+    pub(crate) fn apply_before_instantiation(&self, name: &str) -> Result<(), BeanError> {
+        let post_processors = self.post_processors.read().unwrap().clone();
+        for post_processor in post_processors {
+            post_processor
+                .before_instantiation(name)
+                .map_err(|e| BeanError::CreationFailed {
+                    name: name.to_string(),
+                    message: e.to_string(),
+                })?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn apply_before_init(&self, bean: &mut BeanWrapper) -> Result<(), BeanError> {
+        let post_processors = self.post_processors.read().unwrap().clone();
+        for post_processor in post_processors {
+            post_processor
+                .before_init(bean)
+                .map_err(|e| BeanError::CreationFailed {
+                    name: bean.get_name().to_string(),
+                    message: e.to_string(),
+                })?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn apply_after_init(&self, bean: &mut BeanWrapper) -> Result<(), BeanError> {
+        let post_processors = self.post_processors.read().unwrap().clone();
+        for post_processor in post_processors {
+            post_processor
+                .after_init(bean)
+                .map_err(|e| BeanError::CreationFailed {
+                    name: bean.get_name().to_string(),
+                    message: e.to_string(),
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Run the post processors on a bean registered as a ready-made instance.
+    fn post_process<T: ?Sized + Send + Sync + 'static>(
+        &self,
+        name: &str,
+        bean: Arc<T>,
+    ) -> Result<Arc<T>, BeanError> {
+        let mut wrapper = BeanWrapper::new(name, bean.clone());
+        self.apply_before_init(&mut wrapper)?;
+        self.apply_after_init(&mut wrapper)?;
+        Ok(wrapper.get::<T>().unwrap_or(bean))
+    }
+
+    pub(crate) fn register<T: ?Sized + Send + Sync + 'static>(
+        &self,
+        name: &str,
+        bean: Arc<T>,
+    ) -> Result<(), BeanError> {
+        let address = get_address(&bean);
+        let mut named_beans = self.named_beans.write().unwrap();
+        let named_bean = named_beans.iter_mut().find(|bean| bean.name == name);
+        match named_bean {
+            Some(named_bean) if named_bean.get::<T>().is_none() => {
+                named_bean.instances.push(Box::new(bean.clone()));
+            }
+            Some(_) => {
+                return Err(BeanError::DuplicateBeanName {
+                    name: name.to_string(),
+                })
+            }
+            None => named_beans.push(NamedBean {
+                name: name.to_string(),
+                instances: vec![Box::new(bean.clone())],
+                addresses: vec![address],
+            }),
+        }
+        // the first bean of a type is the one resolved by type
+        self.beans.set::<Arc<T>>(bean);
+        Ok(())
+    }
+}
+
+/// The address of the instance `bean` points to, the same for every type it is seen as.
+fn get_address<T: ?Sized>(bean: &Arc<T>) -> usize {
+    Arc::as_ptr(bean) as *const () as usize
+}
+
+/// The creation path of the current task followed by `name`, an error if `name` is in it already.
+fn get_creation_path(name: &str) -> Result<Vec<String>, BeanError> {
+    let mut path = CREATION_PATH
+        .try_with(|path| path.clone())
+        .unwrap_or_default();
+    if let Some(index) = path.iter().position(|bean_name| bean_name == name) {
+        let mut cycle = path[index..].to_vec();
+        cycle.push(name.to_string());
+        return Err(BeanError::CircularDependency { cycle });
+    }
+    path.push(name.to_string());
+    Ok(path)
 }
 
 impl BeanFactory for DefaultListableBeanFactory {
-    fn get<T: 'static>(&self) -> &T {
-        self.beans.get::<T>()
+    fn get<T: ?Sized + 'static>(&self) -> &T {
+        if let Some(bean) = self.try_get::<T>() {
+            return bean;
+        }
+        let scoped = {
+            let definitions = self.definitions.read().unwrap();
+            definitions.iter().any(|definition| {
+                definition.get_scope() != Scope::Singleton
+                    && definition.provides(TypeId::of::<Arc<T>>())
+            })
+        };
+        if scoped {
+            panic!(
+                "The bean of type '{}' is prototype or request scoped, look it up with get_bean",
+                type_name::<T>()
+            );
+        }
+        panic!("No bean of type '{}' is registered", type_name::<T>())
+    }
+
+    fn try_get<T: ?Sized + 'static>(&self) -> Option<&T> {
+        if self.beans.try_get::<Arc<T>>().is_none() {
+            if let Err(e) = self.create_beans_of_type(TypeId::of::<Arc<T>>(), false) {
+                error!("{}", e);
+            }
+        }
+        match self.beans.try_get::<Arc<T>>() {
+            Some(bean) => Some(bean.as_ref()),
+            None => self.get_parent_bean_factory()?.try_get::<T>(),
+        }
+    }
+
+    fn get_bean<T: ?Sized + Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        self.get_local_bean::<T>()
+            .or_else(|| self.get_parent_bean_factory()?.get_bean::<T>())
+    }
+
+    fn get_named<T: ?Sized + Send + Sync + 'static>(&self, name: &str) -> Option<Arc<T>> {
+        if !self.contains_local_bean(name) {
+            return self.get_parent_bean_factory()?.get_named::<T>(name);
+        }
+        if !self.contains_singleton(name) {
+            let definition = self.get_definition(name)?;
+            let created = match definition.get_scope() {
+                Scope::Singleton => block_on(self.create_bean(definition)).map(|_| None),
+                _ => self.get_scoped::<T>(definition.as_ref()),
+            };
+            match created {
+                Ok(None) => {}
+                Ok(Some(bean)) => return Some(bean),
+                Err(e) => {
+                    error!("{}", e);
+                    return None;
+                }
+            }
+        }
+        let named_beans = self.named_beans.read().unwrap();
+        named_beans
+            .iter()
+            .find(|bean| bean.name == name)
+            .and_then(|bean| bean.get::<T>())
+            .cloned()
+    }
+
+    fn get_all<T: ?Sized + Send + Sync + 'static>(&self) -> Vec<Arc<T>> {
+        if let Err(e) = self.create_beans_of_type(TypeId::of::<Arc<T>>(), true) {
+            error!("{}", e);
+        }
+        let mut beans: Vec<Arc<T>> = {
+            let named_beans = self.named_beans.read().unwrap();
+            named_beans
+                .iter()
+                .filter_map(|bean| bean.get::<T>())
+                .cloned()
+                .collect()
+        };
+        let scoped_definitions: Vec<_> = self
+            .definitions
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|definition| definition.provides(TypeId::of::<Arc<T>>()))
+            .filter(|definition| definition.get_scope() != Scope::Singleton)
+            .cloned()
+            .collect();
+        for definition in scoped_definitions {
+            match self.get_scoped::<T>(definition.as_ref()) {
+                Ok(bean) => beans.extend(bean),
+                Err(BeanError::ScopeNotActive { .. }) => {}
+                Err(e) => error!("{}", e),
+            }
+        }
+        if let Some(parent) = self.get_parent_bean_factory() {
+            beans.extend(parent.get_all::<T>());
+        }
+        beans
     }
 
-    fn try_get<T: 'static>(&self) -> Option<&T> {
-        self.beans.try_get::<T>()
+    fn contains_bean(&self, name: &str) -> bool {
+        self.contains_local_bean(name)
+            || self
+                .get_parent_bean_factory()
+                .is_some_and(|parent| parent.contains_bean(name))
     }
 }
 
 impl ListableBeanFactory for DefaultListableBeanFactory {
     fn get_bean_definition_count(&self) -> usize {
-        self.beans.len()
+        self.get_bean_names().len()
+    }
+
+    fn contains_bean_of_type<T: ?Sized + Send + Sync + 'static>(&self) -> bool {
+        let registered = {
+            let named_beans = self.named_beans.read().unwrap();
+            named_beans.iter().any(|bean| bean.get::<T>().is_some())
+        };
+        registered
+            || {
+                let definitions = self.definitions.read().unwrap();
+                definitions
+                    .iter()
+                    .any(|definition| definition.provides(TypeId::of::<Arc<T>>()))
+            }
+            || self
+                .get_parent_bean_factory()
+                .is_some_and(|parent| parent.contains_bean_of_type::<T>())
+    }
+
+    fn get_bean_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = {
+            let named_beans = self.named_beans.read().unwrap();
+            named_beans.iter().map(|bean| bean.name.clone()).collect()
+        };
+        let definitions = self.definitions.read().unwrap();
+        for definition in definitions.iter() {
+            if !names.iter().any(|name| name == definition.get_name()) {
+                names.push(definition.get_name().to_string());
+            }
+        }
+        names
     }
 }
 
 impl ConfigurableBeanFactory for DefaultListableBeanFactory {
-    fn set<T: Send + Sync + 'static>(&self, state: T) -> bool {
-        self.beans.set(state)
+    fn set<T: Send + Sync + 'static>(&self, state: T) -> Result<(), BeanError> {
+        if self.beans.try_get::<Arc<T>>().is_some() {
+            return Err(BeanError::DuplicateBeanType {
+                type_name: type_name::<T>(),
+            });
+        }
+        let bean = self.post_process(type_name::<T>(), Arc::new(state))?;
+        self.register(type_name::<T>(), bean)
+    }
+
+    fn set_named<T: Send + Sync + 'static>(&self, name: &str, state: T) -> Result<(), BeanError> {
+        if self.contains_local_bean(name) {
+            return Err(BeanError::DuplicateBeanName {
+                name: name.to_string(),
+            });
+        }
+        let bean = self.post_process(name, Arc::new(state))?;
+        self.register(name, bean)
+    }
+
+    fn set_as<I: ?Sized + Send + Sync + 'static>(
+        &self,
+        name: &str,
+        bean: Arc<I>,
+    ) -> Result<(), BeanError> {
+        if !self.contains_singleton(name) && self.get_definition(name).is_some() {
+            return Err(BeanError::DuplicateBeanName {
+                name: name.to_string(),
+            });
+        }
+        let address = get_address(&bean);
+        let registered = {
+            let named_beans = self.named_beans.read().unwrap();
+            named_beans
+                .iter()
+                .find(|named_bean| named_bean.name == name)
+                .map(|named_bean| named_bean.addresses.contains(&address))
+        };
+        if registered == Some(false) {
+            return Err(BeanError::DifferentInstance {
+                name: name.to_string(),
+            });
+        }
+        // the post processors already ran on the instance of a bean adding a trait
+        let bean = match registered {
+            Some(true) => bean,
+            _ => self.post_process(name, bean)?,
+        };
+        if let Some(initializing_bean) =
+            (&bean as &dyn Any).downcast_ref::<Arc<dyn InitializingBean>>()
+        {
+            block_on(initializing_bean.after_properties_set()).map_err(|e| {
+                BeanError::CreationFailed {
+                    name: name.to_string(),
+                    message: e.to_string(),
+                }
+            })?;
+        }
+        self.register(name, bean)?;
+        if registered.is_none() {
+            let mut named_beans = self.named_beans.write().unwrap();
+            if let Some(named_bean) = named_beans.iter_mut().find(|bean| bean.name == name) {
+                if !named_bean.addresses.contains(&address) {
+                    named_bean.addresses.push(address);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn register_bean_definition<T: Send + Sync + 'static>(
+        &self,
+        definition: BeanDefinition<T>,
+    ) -> Result<(), BeanError> {
+        let mut definitions = self.definitions.write().unwrap();
+        let name = definition.get_name();
+        if self.contains_singleton(name)
+            || definitions
+                .iter()
+                .any(|definition| definition.get_name() == name)
+        {
+            return Err(BeanError::DuplicateBeanName {
+                name: name.to_string(),
+            });
+        }
+        definitions.push(Arc::new(definition));
+        Ok(())
+    }
+
+    fn add_bean_post_processor(&self, post_processor: Arc<dyn BeanPostProcessor>) {
+        self.post_processors.write().unwrap().push(post_processor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    trait Greeter: Send + Sync {
+        fn greet(&self) -> String;
+    }
+
+    trait Named: Send + Sync {
+        fn name(&self) -> String;
+    }
+
+    struct English;
+
+    impl Greeter for English {
+        fn greet(&self) -> String {
+            "hello".to_string()
+        }
+    }
+
+    impl Named for English {
+        fn name(&self) -> String {
+            "english".to_string()
+        }
+    }
+
+    struct Counter(usize);
+
+    #[test]
+    fn a_name_is_registered_once() {
+        let bean_factory = DefaultListableBeanFactory::default();
+        bean_factory.set_named("counter", Counter(1)).unwrap();
+        let definition = BeanDefinition::new("lazy", |_| Ok(Counter(2))).lazy();
+        bean_factory.register_bean_definition(definition).unwrap();
+
+        let duplicate = |name: &str| {
+            Err(BeanError::DuplicateBeanName {
+                name: name.to_string(),
+            })
+        };
+        assert_eq!(
+            bean_factory.set_named("counter", Counter(3)),
+            duplicate("counter")
+        );
+        assert_eq!(
+            bean_factory.set_named("lazy", Counter(3)),
+            duplicate("lazy")
+        );
+        let definition = BeanDefinition::new("counter", |_| Ok(Counter(3)));
+        assert_eq!(
+            bean_factory.register_bean_definition(definition),
+            duplicate("counter")
+        );
+        assert_eq!(bean_factory.get_named::<Counter>("counter").unwrap().0, 1);
+    }
+
+    /// An async factory waiting on the runtime, it never completes when blocked on in a
+    /// current-thread runtime.
+    fn timer_definition(name: &str, value: usize) -> BeanDefinition<Counter> {
+        BeanDefinition::new_async(name, move |_| {
+            Box::pin(async move {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                Ok(Counter(value))
+            })
+        })
+        .lazy()
+    }
+
+    #[tokio::test]
+    async fn async_lookups_await_the_factories() {
+        let parent = DefaultListableBeanFactory::default();
+        parent
+            .register_bean_definition(timer_definition("parent", 0))
+            .unwrap();
+        let bean_factory = DefaultListableBeanFactory::default();
+        bean_factory
+            .set_parent_bean_factory(Arc::new(Parent(parent)))
+            .unwrap();
+        bean_factory
+            .register_bean_definition(timer_definition("first", 1))
+            .unwrap();
+        bean_factory
+            .register_bean_definition(timer_definition("second", 2))
+            .unwrap();
+
+        let first = bean_factory.get_bean_async::<Counter>().await;
+        let second = bean_factory.get_named_async::<Counter>("second").await;
+        let parent = bean_factory.get_named_async::<Counter>("parent").await;
+        let all = bean_factory.get_all_async::<Counter>().await;
+
+        assert_eq!(second.unwrap().0, 2);
+        assert_eq!(first.unwrap().0, 1);
+        assert_eq!(parent.unwrap().0, 0);
+        let all: Vec<usize> = all.iter().map(|counter| counter.0).collect();
+        assert_eq!(all, vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn a_type_is_set_once() {
+        let bean_factory = DefaultListableBeanFactory::default();
+        bean_factory.set(Counter(1)).unwrap();
+
+        assert_eq!(
+            bean_factory.set(Counter(2)),
+            Err(BeanError::DuplicateBeanType {
+                type_name: type_name::<Counter>()
+            })
+        );
+        // another bean of the type can be registered under its own name
+        bean_factory.set_named("other", Counter(3)).unwrap();
+        assert_eq!(bean_factory.get::<Counter>().0, 1);
+    }
+
+    struct Parent(DefaultListableBeanFactory);
+
+    impl ParentBeanFactory for Parent {
+        fn get_bean_factory(&self) -> &DefaultListableBeanFactory {
+            &self.0
+        }
+    }
+
+    #[test]
+    fn get_all_returns_the_beans_by_registration_then_the_parent_beans() {
+        let parent = DefaultListableBeanFactory::default();
+        parent.set_named("parent", Counter(0)).unwrap();
+        let bean_factory = DefaultListableBeanFactory::default();
+        bean_factory
+            .set_parent_bean_factory(Arc::new(Parent(parent)))
+            .unwrap();
+        bean_factory.set_named("b", Counter(1)).unwrap();
+        let definition = BeanDefinition::new("c", |_| Ok(Counter(3))).lazy();
+        bean_factory.register_bean_definition(definition).unwrap();
+        bean_factory.set_named("a", Counter(2)).unwrap();
+
+        let counters: Vec<usize> = bean_factory
+            .get_all::<Counter>()
+            .iter()
+            .map(|counter| counter.0)
+            .collect();
+
+        // the lazy bean is created by the lookup, after the beans registered
+        assert_eq!(counters, vec![1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn circular_dependencies_are_reported() {
+        let bean_factory = DefaultListableBeanFactory::default();
+        for (name, dependency) in [("a", "b"), ("b", "c"), ("c", "a")] {
+            let definition = BeanDefinition::new(name, |_| Ok(Counter(0))).depends_on(dependency);
+            bean_factory
+                .register_bean_definition(definition.lazy())
+                .unwrap();
+        }
+        let cycle = ["a", "b", "c", "a"].map(String::from).to_vec();
+        assert_eq!(
+            block_on(bean_factory.create_bean(bean_factory.get_definition("a").unwrap())),
+            Err(BeanError::CircularDependency { cycle })
+        );
+        assert!(bean_factory.get_named::<Counter>("a").is_none());
+    }
+
+    struct Service(usize);
+
+    #[test]
+    fn type_dependencies_are_created_first() {
+        let bean_factory = DefaultListableBeanFactory::default();
+        let service = BeanDefinition::new("service", |bean_factory| {
+            // created by the factory before this one, not by the lookup
+            assert!(bean_factory.contains_singleton("counter"));
+            let counter = bean_factory.get_bean::<Counter>().ok_or("no counter")?;
+            Ok(Service(counter.0))
+        })
+        .depends_on_type::<Counter>();
+        bean_factory.register_bean_definition(service).unwrap();
+        let counter = BeanDefinition::new("counter", |_| Ok(Counter(7))).lazy();
+        bean_factory.register_bean_definition(counter).unwrap();
+        block_on(bean_factory.preinstantiate_singletons()).unwrap();
+        assert_eq!(bean_factory.get::<Service>().0, 7);
+    }
+
+    #[test]
+    fn circular_lookups_are_reported() {
+        let bean_factory = DefaultListableBeanFactory::default();
+        let definition = BeanDefinition::new("counter", |bean_factory| {
+            match bean_factory.get_bean::<Counter>() {
+                Some(counter) => Ok(Counter(counter.0 + 1)),
+                None => Err("no counter".into()),
+            }
+        });
+        bean_factory.register_bean_definition(definition).unwrap();
+        let result = block_on(bean_factory.preinstantiate_singletons());
+        assert!(matches!(result, Err(BeanError::CreationFailed { .. })));
+        assert!(bean_factory.try_get::<Counter>().is_none());
+    }
+
+    #[test]
+    fn concurrent_lookups_create_a_lazy_bean_once() {
+        let created = Arc::new(Mutex::new(0));
+        let bean_factory = DefaultListableBeanFactory::default();
+        let counter = created.clone();
+        let definition = BeanDefinition::new_async("counter", move |_| {
+            let created = counter.clone();
+            Box::pin(async move {
+                async_std::task::sleep(std::time::Duration::from_millis(50)).await;
+                let mut created = created.lock().unwrap();
+                *created += 1;
+                Ok(Counter(*created))
+            })
+        });
+        bean_factory
+            .register_bean_definition(definition.lazy())
+            .unwrap();
+        std::thread::scope(|scope| {
+            let by_type = scope.spawn(|| bean_factory.get_bean::<Counter>().map(|bean| bean.0));
+            let all = scope.spawn(|| bean_factory.get_all::<Counter>().len());
+            assert_eq!(by_type.join().unwrap(), Some(1));
+            assert_eq!(all.join().unwrap(), 1);
+        });
+        assert_eq!(*created.lock().unwrap(), 1);
+    }
+
+    fn counter_definition(scope: Scope) -> BeanDefinition<Counter> {
+        let created = Arc::new(Mutex::new(0));
+        BeanDefinition::new("counter", move |_| {
+            let mut created = created.lock().unwrap();
+            *created += 1;
+            Ok(Counter(*created))
+        })
+        .scope(scope)
+    }
+
+    #[test]
+    fn prototype_beans_are_created_on_every_lookup() {
+        let bean_factory = DefaultListableBeanFactory::default();
+        bean_factory
+            .register_bean_definition(counter_definition(Scope::Prototype))
+            .unwrap();
+        block_on(bean_factory.preinstantiate_singletons()).unwrap();
+        let first = bean_factory.get_bean::<Counter>().unwrap();
+        let second = bean_factory.get_named::<Counter>("counter").unwrap();
+        assert_eq!((first.0, second.0), (1, 2));
+        assert_eq!(bean_factory.get_all::<Counter>()[0].0, 3);
+        assert!(bean_factory.try_get::<Counter>().is_none());
+    }
+
+    #[tokio::test]
+    async fn request_scoped_beans_live_as_long_as_the_request() {
+        let bean_factory = DefaultListableBeanFactory::default();
+        bean_factory
+            .register_bean_definition(counter_definition(Scope::Request))
+            .unwrap();
+        assert!(bean_factory.get_bean::<Counter>().is_none());
+        assert!(bean_factory.get_all::<Counter>().is_empty());
+        let (first, second) = RequestScope::run(async {
+            let first = bean_factory.get_bean::<Counter>().unwrap();
+            (first, bean_factory.get_named::<Counter>("counter").unwrap())
+        })
+        .await;
+        assert!(Arc::ptr_eq(&first, &second));
+        let next = RequestScope::run(async { bean_factory.get_bean::<Counter>().unwrap() }).await;
+        assert_eq!((first.0, next.0), (1, 2));
+    }
+
+    /// Records the beans it processes and how long their factory function ran.
+    #[derive(Default)]
+    struct CreationTimes {
+        instantiating: Mutex<Vec<String>>,
+        created: Mutex<Vec<(String, std::time::Duration)>>,
+    }
+
+    impl BeanPostProcessor for CreationTimes {
+        fn before_instantiation(
+            &self,
+            name: &str,
+        ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            self.instantiating.lock().unwrap().push(name.to_string());
+            Ok(())
+        }
+
+        fn after_init(
+            &self,
+            bean: &mut BeanWrapper,
+        ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            let created = (bean.get_name().to_string(), bean.get_instantiation_time());
+            self.created.lock().unwrap().push(created);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn post_processors_run_once_per_bean() {
+        let bean_factory = DefaultListableBeanFactory::default();
+        let creation_times = Arc::new(CreationTimes::default());
+        bean_factory.add_bean_post_processor(creation_times.clone());
+        let definition = BeanDefinition::new_async("counter", |_| {
+            Box::pin(async {
+                async_std::task::sleep(std::time::Duration::from_millis(20)).await;
+                Ok(Counter(1))
+            })
+        });
+        bean_factory.register_bean_definition(definition).unwrap();
+        block_on(bean_factory.preinstantiate_singletons()).unwrap();
+        let bean = Arc::new(English);
+        bean_factory
+            .set_as::<dyn Greeter>("english", bean.clone())
+            .unwrap();
+        bean_factory.set_as::<dyn Named>("english", bean).unwrap();
+
+        assert_eq!(
+            *creation_times.instantiating.lock().unwrap(),
+            vec!["counter"]
+        );
+        let created = creation_times.created.lock().unwrap();
+        let names: Vec<&str> = created.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["counter", "english"]);
+        assert!(created[0].1 >= std::time::Duration::from_millis(20));
+        assert_eq!(created[1].1, std::time::Duration::ZERO);
+    }
+
+    #[derive(Default)]
+    struct Pool {
+        events: Mutex<Vec<&'static str>>,
+    }
+
+    #[async_trait::async_trait]
+    impl InitializingBean for Pool {
+        async fn after_properties_set(
+            &self,
+        ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            self.events.lock().unwrap().push("init");
+            Ok(())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl DisposableBean for Pool {
+        async fn destroy(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            self.events.lock().unwrap().push("destroy");
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn set_as_runs_the_lifecycle_callbacks() {
+        let bean_factory = DefaultListableBeanFactory::default();
+        let pool = Arc::new(Pool::default());
+        bean_factory.set_as("pool", pool.clone()).unwrap();
+        bean_factory
+            .set_as::<dyn InitializingBean>("pool", pool.clone())
+            .unwrap();
+        bean_factory
+            .set_as::<dyn DisposableBean>("pool", pool.clone())
+            .unwrap();
+        block_on(bean_factory.destroy_singletons()).unwrap();
+        assert_eq!(*pool.events.lock().unwrap(), vec!["init", "destroy"]);
+    }
+
+    #[test]
+    fn set_as_adds_traits_of_the_same_instance() {
+        let bean_factory = DefaultListableBeanFactory::default();
+        let bean = Arc::new(English);
+        bean_factory
+            .set_as::<dyn Greeter>("english", bean.clone())
+            .unwrap();
+        bean_factory.set_as::<dyn Named>("english", bean).unwrap();
+        assert_eq!(bean_factory.get::<dyn Greeter>().greet(), "hello");
+        assert_eq!(
+            bean_factory
+                .get_named::<dyn Named>("english")
+                .unwrap()
+                .name(),
+            "english"
+        );
+    }
+
+    #[test]
+    fn set_as_rejects_another_instance() {
+        let bean_factory = DefaultListableBeanFactory::default();
+        bean_factory
+            .set_as::<dyn Greeter>("english", Arc::new(English))
+            .unwrap();
+        let result = bean_factory.set_as::<dyn Named>("english", Arc::new(English));
+        assert_eq!(
+            result,
+            Err(BeanError::DifferentInstance {
+                name: "english".to_string()
+            })
+        );
+        assert!(bean_factory.try_get::<dyn Named>().is_none());
+    }
+
+    #[test]
+    fn set_as_adds_traits_to_a_bean_registered_with_set() {
+        let bean_factory = DefaultListableBeanFactory::default();
+        bean_factory.set(English).unwrap();
+        bean_factory.set_named("french", English).unwrap();
+
+        let english = bean_factory.get_bean::<English>().unwrap();
+        bean_factory
+            .set_as::<dyn Greeter>(type_name::<English>(), english)
+            .unwrap();
+        let french = bean_factory.get_named::<English>("french").unwrap();
+        bean_factory
+            .set_as::<dyn Named>("french", french.clone())
+            .unwrap();
+
+        assert_eq!(bean_factory.get::<dyn Greeter>().greet(), "hello");
+        let named = bean_factory.get_named::<dyn Named>("french").unwrap();
+        assert_eq!(get_address(&named), get_address(&french));
+        let result = bean_factory.set_as::<dyn Greeter>("french", Arc::new(English));
+        assert_eq!(
+            result,
+            Err(BeanError::DifferentInstance {
+                name: "french".to_string()
+            })
+        );
     }
 }
@@ -0,0 +1,21 @@
+use crate::factory::bean_wrapper::BeanWrapper;
+use std::error::Error;
+
+/// Hook called once for every bean entering the bean factory, around the
+/// [`InitializingBean`](crate::factory::initializing_bean::InitializingBean) callback.
+/// Both methods may inspect the bean or replace its instances.
+pub trait BeanPostProcessor: Send + Sync {
+    /// Called before the factory function of a bean definition runs, the time it took is
+    /// given by [`BeanWrapper::get_instantiation_time`].
+    fn before_instantiation(&self, _name: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        Ok(())
+    }
+
+    fn before_init(&self, _bean: &mut BeanWrapper) -> Result<(), Box<dyn Error + Send + Sync>> {
+        Ok(())
+    }
+
+    fn after_init(&self, _bean: &mut BeanWrapper) -> Result<(), Box<dyn Error + Send + Sync>> {
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use std::any::{type_name, Any};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A bean being created, with the instances it is registered under: the `Arc<T>` of
+/// its concrete type and one per trait-object type.
+pub struct BeanWrapper {
+    name: String,
+    type_name: &'static str,
+    instances: Vec<Box<dyn Any + Send + Sync>>,
+    instantiation_time: Duration,
+}
+
+impl BeanWrapper {
+    pub fn new<T: ?Sized + Send + Sync + 'static>(name: &str, bean: Arc<T>) -> Self {
+        BeanWrapper {
+            name: name.to_string(),
+            type_name: type_name::<T>(),
+            instances: vec![Box::new(bean)],
+            instantiation_time: Duration::ZERO,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// The type the bean was created as.
+    pub fn get_type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// How long the factory function of the bean definition ran, zero for a bean registered
+    /// as an instance.
+    pub fn get_instantiation_time(&self) -> Duration {
+        self.instantiation_time
+    }
+
+    pub fn get<T: ?Sized + Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        self.instances
+            .iter()
+            .find_map(|instance| instance.downcast_ref::<Arc<T>>())
+            .cloned()
+    }
+
+    /// Replace the instance of type `T`, e.g. wrap the `dyn Service` of the bean in a
+    /// tracing proxy. Returns false if the bean is not registered as `T`.
+    pub fn replace<T: ?Sized + Send + Sync + 'static>(&mut self, bean: Arc<T>) -> bool {
+        match self
+            .instances
+            .iter_mut()
+            .find_map(|instance| instance.downcast_mut::<Arc<T>>())
+        {
+            Some(instance) => {
+                *instance = bean;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn set_instantiation_time(&mut self, instantiation_time: Duration) {
+        self.instantiation_time = instantiation_time;
+    }
+
+    pub(crate) fn add(&mut self, instance: Box<dyn Any + Send + Sync>) {
+        self.instances.push(instance);
+    }
+
+    pub(crate) fn into_instances(self) -> Vec<Box<dyn Any + Send + Sync>> {
+        self.instances
+    }
+}
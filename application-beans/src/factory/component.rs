@@ -0,0 +1,76 @@
+use crate::factory::bean_definition::BeanCreationResult;
+use crate::factory::bean_error::BeanError;
+use crate::factory::bean_factory::{BeanFactory, DefaultListableBeanFactory};
+use application_core::env::environment::ApplicationEnvironment;
+use application_core::env::property_resolver::PropertyResolver;
+pub use inventory::submit;
+use serde::de::DeserializeOwned;
+use std::any::type_name;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// The name the application context registers its `RwLock<ApplicationEnvironment>` under.
+pub const ENVIRONMENT_BEAN_NAME: &str = "environment";
+
+/// Registers the bean definition of a type marked with `#[derive(Component)]`
+pub trait ComponentFactory: Send + Sync + 'static {
+    fn register(&self, bean_factory: &DefaultListableBeanFactory) -> Result<(), BeanError>;
+}
+
+inventory::collect!(&'static dyn ComponentFactory);
+
+/// auto register
+#[macro_export]
+macro_rules! submit_component {
+    ($ty:ident) => {
+        ::application_beans::factory::component::submit! {
+            &$ty as &dyn ::application_beans::factory::component::ComponentFactory
+        }
+    };
+}
+
+/// Register the bean definitions of all components.
+pub fn register_components(bean_factory: &DefaultListableBeanFactory) -> Result<(), BeanError> {
+    for component in inventory::iter::<&dyn ComponentFactory> {
+        component.register(bean_factory)?;
+    }
+    Ok(())
+}
+
+/// Resolve a component field from the bean of type `T`.
+pub fn resolve_bean<T: ?Sized + Send + Sync + 'static>(
+    bean_factory: &DefaultListableBeanFactory,
+) -> BeanCreationResult<Arc<T>> {
+    bean_factory
+        .get_bean::<T>()
+        .ok_or_else(|| format!("No bean of type '{}' is registered", type_name::<T>()).into())
+}
+
+/// Resolve a component field from the bean registered under `name`.
+pub fn resolve_named_bean<T: ?Sized + Send + Sync + 'static>(
+    bean_factory: &DefaultListableBeanFactory,
+    name: &str,
+) -> BeanCreationResult<Arc<T>> {
+    bean_factory.get_named::<T>(name).ok_or_else(|| {
+        format!(
+            "No bean named '{}' of type '{}' is registered",
+            name,
+            type_name::<T>()
+        )
+        .into()
+    })
+}
+
+/// Resolve a component field from the property `key` of the environment.
+pub async fn resolve_value<T: DeserializeOwned>(
+    bean_factory: &DefaultListableBeanFactory,
+    key: &str,
+) -> BeanCreationResult<T> {
+    let environment = bean_factory
+        .get_named::<RwLock<ApplicationEnvironment>>(ENVIRONMENT_BEAN_NAME)
+        .ok_or("No environment is registered")?;
+    let environment = environment.read().await;
+    environment
+        .get_property::<T>(key)
+        .ok_or_else(|| format!("Property '{}' is not set", key).into())
+}
@@ -0,0 +1,256 @@
+use crate::factory::bean_factory::{DefaultListableBeanFactory, ListableBeanFactory};
+use crate::factory::condition_evaluation_report::ConditionEvaluationReport;
+use application_core::env::environment::{ApplicationEnvironment, Environment};
+use application_core::env::property_resolver::PropertyResolver;
+use std::any::type_name;
+
+/// What a condition is evaluated against.
+pub struct ConditionContext<'a> {
+    pub environment: &'a ApplicationEnvironment,
+    pub bean_factory: &'a DefaultListableBeanFactory,
+}
+
+/// Decides whether an initializer or a bean definition is applied.
+pub trait Condition: Send + Sync {
+    fn matches(&self, context: &ConditionContext) -> bool;
+
+    /// Describes the condition in the [`ConditionEvaluationReport`].
+    fn describe(&self) -> String;
+}
+
+/// Whether all `conditions` of `source` match, the outcomes are recorded in the
+/// [`ConditionEvaluationReport`] of the bean factory.
+pub fn matches_all(
+    source: &str,
+    conditions: &[Box<dyn Condition>],
+    context: &ConditionContext,
+) -> bool {
+    let report = ConditionEvaluationReport::get(context.bean_factory);
+    let mut matched = true;
+    for condition in conditions {
+        let outcome = condition.matches(context);
+        report.record(source, condition.describe(), outcome);
+        matched &= outcome;
+    }
+    matched
+}
+
+/// Matches when the property `key` equals `having_value`, ignoring case. An empty
+/// `having_value` matches any value other than `false`.
+pub fn on_property(key: &str, having_value: &str) -> OnPropertyCondition {
+    OnPropertyCondition {
+        key: key.to_string(),
+        having_value: having_value.to_string(),
+        match_if_missing: false,
+    }
+}
+
+/// Matches when a bean of type `T` is registered or defined.
+pub fn on_bean<T: ?Sized + Send + Sync + 'static>() -> OnBeanCondition {
+    OnBeanCondition {
+        type_name: type_name::<T>(),
+        contains_bean: DefaultListableBeanFactory::contains_bean_of_type::<T>,
+        missing: false,
+    }
+}
+
+/// Matches when no bean of type `T` is registered or defined.
+pub fn on_missing_bean<T: ?Sized + Send + Sync + 'static>() -> OnBeanCondition {
+    OnBeanCondition {
+        type_name: type_name::<T>(),
+        contains_bean: DefaultListableBeanFactory::contains_bean_of_type::<T>,
+        missing: true,
+    }
+}
+
+/// Matches when `profile` is active, or when it is not active if prefixed with `!`.
+pub fn on_profile(profile: &str) -> OnProfileCondition {
+    OnProfileCondition {
+        profile: profile.to_string(),
+    }
+}
+
+pub struct OnPropertyCondition {
+    key: String,
+    having_value: String,
+    match_if_missing: bool,
+}
+
+impl OnPropertyCondition {
+    /// Also match when the property is not set.
+    pub fn match_if_missing(mut self) -> Self {
+        self.match_if_missing = true;
+        self
+    }
+}
+
+impl Condition for OnPropertyCondition {
+    fn matches(&self, context: &ConditionContext) -> bool {
+        match context.environment.get_property::<String>(&self.key) {
+            Some(value) if self.having_value.is_empty() => !value.eq_ignore_ascii_case("false"),
+            Some(value) => value.eq_ignore_ascii_case(&self.having_value),
+            None => self.match_if_missing,
+        }
+    }
+
+    fn describe(&self) -> String {
+        if self.having_value.is_empty() {
+            format!("on_property {}", self.key)
+        } else {
+            format!("on_property {}={}", self.key, self.having_value)
+        }
+    }
+}
+
+pub struct OnBeanCondition {
+    type_name: &'static str,
+    contains_bean: fn(&DefaultListableBeanFactory) -> bool,
+    missing: bool,
+}
+
+impl Condition for OnBeanCondition {
+    fn matches(&self, context: &ConditionContext) -> bool {
+        (self.contains_bean)(context.bean_factory) != self.missing
+    }
+
+    fn describe(&self) -> String {
+        if self.missing {
+            format!("on_missing_bean {}", self.type_name)
+        } else {
+            format!("on_bean {}", self.type_name)
+        }
+    }
+}
+
+pub struct OnProfileCondition {
+    profile: String,
+}
+
+impl Condition for OnProfileCondition {
+    fn matches(&self, context: &ConditionContext) -> bool {
+        let active_profiles = context.environment.get_active_profiles();
+        match self.profile.strip_prefix('!') {
+            Some(profile) => !active_profiles.iter().any(|active| active == profile),
+            None => active_profiles.contains(&self.profile),
+        }
+    }
+
+    fn describe(&self) -> String {
+        format!("on_profile {}", self.profile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::factory::bean_definition::BeanDefinition;
+    use crate::factory::bean_factory::ConfigurableBeanFactory;
+    use application_core::env::environment::ConfigurableEnvironment;
+    use application_core::env::property::PropertySource;
+    use config::Config;
+
+    struct Cache;
+
+    fn environment(properties: &[(&str, &str)]) -> ApplicationEnvironment {
+        let mut builder = Config::builder();
+        for (key, value) in properties {
+            builder = builder.set_override(*key, *value).unwrap();
+        }
+        let mut environment = ApplicationEnvironment::new(vec!["dev".to_string()], None, None);
+        environment.add_property_source(PropertySource {
+            name: "test".to_string(),
+            source: builder.build().unwrap(),
+        });
+        environment
+    }
+
+    fn matches(condition: impl Condition, environment: &ApplicationEnvironment) -> bool {
+        let bean_factory = DefaultListableBeanFactory::default();
+        condition.matches(&ConditionContext {
+            environment,
+            bean_factory: &bean_factory,
+        })
+    }
+
+    #[test]
+    fn on_property_compares_the_value_ignoring_case() {
+        let environment = environment(&[("cache.enabled", "TRUE"), ("cache.type", "redis")]);
+        assert!(matches(on_property("cache.enabled", "true"), &environment));
+        assert!(!matches(on_property("cache.type", "memory"), &environment));
+        assert!(matches(on_property("cache.type", ""), &environment));
+    }
+
+    #[test]
+    fn on_property_without_value_does_not_match_false() {
+        let environment = environment(&[("cache.enabled", "false")]);
+        assert!(!matches(on_property("cache.enabled", ""), &environment));
+    }
+
+    #[test]
+    fn on_property_match_if_missing() {
+        let environment = environment(&[]);
+        assert!(!matches(on_property("cache.enabled", "true"), &environment));
+        let condition = on_property("cache.enabled", "true").match_if_missing();
+        assert!(matches(condition, &environment));
+        let environment = self::environment(&[("cache.enabled", "false")]);
+        let condition = on_property("cache.enabled", "true").match_if_missing();
+        assert!(!matches(condition, &environment));
+    }
+
+    #[test]
+    fn on_bean_and_on_missing_bean() {
+        let environment = environment(&[]);
+        let bean_factory = DefaultListableBeanFactory::default();
+        let context = ConditionContext {
+            environment: &environment,
+            bean_factory: &bean_factory,
+        };
+        assert!(!on_bean::<Cache>().matches(&context));
+        assert!(on_missing_bean::<Cache>().matches(&context));
+
+        let definition = BeanDefinition::new("cache", |_| Ok(Cache)).lazy();
+        bean_factory.register_bean_definition(definition).unwrap();
+        assert!(on_bean::<Cache>().matches(&context));
+        assert!(!on_missing_bean::<Cache>().matches(&context));
+    }
+
+    #[test]
+    fn on_profile_and_negated_profile() {
+        let environment = environment(&[]);
+        assert!(matches(on_profile("dev"), &environment));
+        assert!(!matches(on_profile("prod"), &environment));
+        assert!(!matches(on_profile("!dev"), &environment));
+        assert!(matches(on_profile("!prod"), &environment));
+    }
+
+    #[test]
+    fn matches_all_records_every_outcome() {
+        let environment = environment(&[("cache.enabled", "true")]);
+        let bean_factory = DefaultListableBeanFactory::default();
+        let context = ConditionContext {
+            environment: &environment,
+            bean_factory: &bean_factory,
+        };
+        let conditions: Vec<Box<dyn Condition>> = vec![
+            Box::new(on_property("cache.enabled", "true")),
+            Box::new(on_profile("prod")),
+        ];
+        assert!(!matches_all("cacheInitializer", &conditions, &context));
+
+        let evaluations = ConditionEvaluationReport::get(&bean_factory).get_evaluations();
+        let outcomes: Vec<(&str, bool)> = evaluations
+            .iter()
+            .map(|evaluation| (evaluation.condition.as_str(), evaluation.matched))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("on_property cache.enabled=true", true),
+                ("on_profile prod", false)
+            ]
+        );
+        assert!(evaluations
+            .iter()
+            .all(|evaluation| evaluation.source == "cacheInitializer"));
+    }
+}
@@ -0,0 +1,68 @@
+use crate::factory::bean_factory::{
+    BeanFactory, ConfigurableBeanFactory, DefaultListableBeanFactory,
+};
+use std::fmt::{Display, Formatter};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone)]
+pub struct ConditionEvaluation {
+    /// The initializer or bean definition the condition belongs to.
+    pub source: String,
+    pub condition: String,
+    pub matched: bool,
+}
+
+/// Records the outcome of every condition evaluated while the application starts.
+#[derive(Default)]
+pub struct ConditionEvaluationReport {
+    evaluations: Mutex<Vec<ConditionEvaluation>>,
+}
+
+impl ConditionEvaluationReport {
+    pub const BEAN_NAME: &'static str = "conditionEvaluationReport";
+
+    /// Get the report registered in `bean_factory`, registering a new one if there is none.
+    pub fn get(bean_factory: &DefaultListableBeanFactory) -> Arc<ConditionEvaluationReport> {
+        if let Some(report) = bean_factory.get_named::<ConditionEvaluationReport>(Self::BEAN_NAME) {
+            return report;
+        }
+        let report = Arc::new(ConditionEvaluationReport::default());
+        // another thread may have registered the report meanwhile
+        match bean_factory.set_as(Self::BEAN_NAME, report.clone()) {
+            Ok(()) => report,
+            Err(_) => bean_factory
+                .get_named::<ConditionEvaluationReport>(Self::BEAN_NAME)
+                .unwrap_or(report),
+        }
+    }
+
+    pub fn record(&self, source: &str, condition: String, matched: bool) {
+        self.evaluations.lock().unwrap().push(ConditionEvaluation {
+            source: source.to_string(),
+            condition,
+            matched,
+        });
+    }
+
+    /// The evaluations recorded so far, in evaluation order.
+    pub fn get_evaluations(&self) -> Vec<ConditionEvaluation> {
+        self.evaluations.lock().unwrap().clone()
+    }
+}
+
+impl Display for ConditionEvaluationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let evaluations = self.evaluations.lock().unwrap();
+        writeln!(f, "Condition evaluation report")?;
+        for (title, matched) in [("Positive matches", true), ("Negative matches", false)] {
+            writeln!(f, "{}:", title)?;
+            for evaluation in evaluations
+                .iter()
+                .filter(|evaluation| evaluation.matched == matched)
+            {
+                writeln!(f, "   {} {}", evaluation.source, evaluation.condition)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use crate::factory::bean_factory::{
+    BeanFactory, ConfigurableBeanFactory, DefaultListableBeanFactory,
+};
+use application_core::env::configuration_properties::{BindError, ConfigurationProperties};
+use application_core::env::environment::ApplicationEnvironment;
+pub use inventory::submit;
+use std::error::Error;
+use std::sync::{Arc, RwLock};
+
+/// Binds a type marked with `#[derive(ConfigurationProperties)]` and registers it as a bean.
+pub trait ConfigurationPropertiesFactory: Send + Sync + 'static {
+    fn bind(
+        &self,
+        environment: &ApplicationEnvironment,
+        bean_factory: &DefaultListableBeanFactory,
+    ) -> Result<(), Box<dyn Error + Send + Sync>>;
+
+    /// Refresh-scoped configurations are registered as [`Refreshable`] and bound again when
+    /// the environment is refreshed.
+    fn is_refresh_scoped(&self) -> bool {
+        false
+    }
+}
+
+inventory::collect!(&'static dyn ConfigurationPropertiesFactory);
+
+/// auto register
+#[macro_export]
+macro_rules! submit_configuration_properties {
+    ($ty:ident) => {
+        ::application_beans::factory::configuration_properties::submit! {
+            &$ty as &dyn ::application_beans::factory::configuration_properties::ConfigurationPropertiesFactory
+        }
+    };
+}
+
+/// Bind the configuration `T` and register it under `name`.
+pub fn register_configuration_properties<T: ConfigurationProperties>(
+    name: &str,
+    environment: &ApplicationEnvironment,
+    bean_factory: &DefaultListableBeanFactory,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let properties = T::bind(environment)?;
+    bean_factory.set_named(name, properties)?;
+    Ok(())
+}
+
+/// A refresh-scoped configuration, [`Refreshable::get`] returns the last bound instance.
+pub struct Refreshable<T> {
+    value: RwLock<Arc<T>>,
+}
+
+impl<T> Refreshable<T> {
+    pub fn new(value: T) -> Self {
+        Refreshable {
+            value: RwLock::new(Arc::new(value)),
+        }
+    }
+
+    pub fn get(&self) -> Arc<T> {
+        self.value.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn set(&self, value: T) {
+        *self.value.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(value);
+    }
+}
+
+/// Bind the configuration `T` and register it under `name` as a [`Refreshable<T>`], or
+/// replace the instance of the one registered already.
+pub fn register_refreshable_configuration_properties<T: ConfigurationProperties>(
+    name: &str,
+    environment: &ApplicationEnvironment,
+    bean_factory: &DefaultListableBeanFactory,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let properties = T::bind(environment)?;
+    match bean_factory.get_named::<Refreshable<T>>(name) {
+        Some(refreshable) => refreshable.set(properties),
+        None => bean_factory.set_named(name, Refreshable::new(properties))?,
+    }
+    Ok(())
+}
+
+/// Bind and register all configuration properties, the invalid properties of all of them
+/// are reported in one [`BindError`].
+pub fn bind_configuration_properties(
+    environment: &ApplicationEnvironment,
+    bean_factory: &DefaultListableBeanFactory,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    bind_all(environment, bean_factory, |_| true)
+}
+
+/// Bind the refresh-scoped configuration properties again, the invalid ones keep their
+/// instance and are reported in one [`BindError`].
+pub fn rebind_configuration_properties(
+    environment: &ApplicationEnvironment,
+    bean_factory: &DefaultListableBeanFactory,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    bind_all(environment, bean_factory, |factory| {
+        factory.is_refresh_scoped()
+    })
+}
+
+fn bind_all(
+    environment: &ApplicationEnvironment,
+    bean_factory: &DefaultListableBeanFactory,
+    filter: impl Fn(&dyn ConfigurationPropertiesFactory) -> bool,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut invalid_properties = vec![];
+    for factory in inventory::iter::<&dyn ConfigurationPropertiesFactory> {
+        if !filter(*factory) {
+            continue;
+        }
+        if let Err(e) = factory.bind(environment, bean_factory) {
+            match e.downcast::<BindError>() {
+                Ok(e) => invalid_properties.extend(e.invalid_properties),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    if !invalid_properties.is_empty() {
+        return Err(Box::new(BindError { invalid_properties }));
+    }
+    Ok(())
+}
@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+use std::error::Error;
+
+/// Implemented by beans that release resources when the context is closed,
+/// register the bean as `dyn DisposableBean` to have it called.
+#[async_trait]
+pub trait DisposableBean: Send + Sync {
+    async fn destroy(&self) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
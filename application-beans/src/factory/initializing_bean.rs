@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+use std::error::Error;
+
+/// Implemented by beans that need to run code once they are created by a bean definition,
+/// register it with `BeanDefinition::with_interface::<dyn InitializingBean>`. A ready-made
+/// bean runs it when it is registered with `set_as::<dyn InitializingBean>`.
+#[async_trait]
+pub trait InitializingBean: Send + Sync {
+    async fn after_properties_set(&self) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
@@ -1 +1,12 @@
+pub mod bean_definition;
+pub mod bean_error;
 pub mod bean_factory;
+pub mod bean_post_processor;
+pub mod bean_wrapper;
+pub mod component;
+pub mod condition;
+pub mod condition_evaluation_report;
+pub mod configuration_properties;
+pub mod disposable_bean;
+pub mod initializing_bean;
+pub mod scope;
@@ -0,0 +1,72 @@
+use crate::factory::bean_error::BeanError;
+use crate::factory::bean_factory::NamedBean;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+/// How long the instances created from a bean definition live.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Scope {
+    /// A single instance shared by every lookup.
+    #[default]
+    Singleton,
+    /// A new instance on every lookup.
+    Prototype,
+    /// One instance per request, dropped when the request completes, see [`RequestScope`].
+    Request,
+}
+
+tokio::task_local! {
+    static REQUEST_SCOPE: Arc<RequestScope>;
+}
+
+/// Holds the request scoped beans of the request being handled.
+#[derive(Default)]
+pub struct RequestScope {
+    beans: Mutex<Vec<NamedBean>>,
+}
+
+impl RequestScope {
+    /// Run `future` in a new request scope, the beans created in it are dropped with the scope.
+    pub async fn run<F: Future>(future: F) -> F::Output {
+        REQUEST_SCOPE
+            .scope(Arc::new(RequestScope::default()), future)
+            .await
+    }
+
+    pub(crate) fn current() -> Option<Arc<RequestScope>> {
+        REQUEST_SCOPE.try_with(|scope| scope.clone()).ok()
+    }
+
+    pub(crate) fn get_or_create<T, F>(
+        &self,
+        name: &str,
+        create: F,
+    ) -> Result<Option<Arc<T>>, BeanError>
+    where
+        T: ?Sized + Send + Sync + 'static,
+        F: FnOnce() -> Result<NamedBean, BeanError>,
+    {
+        if let Some(bean) = self.get::<T>(name) {
+            return Ok(bean);
+        }
+        // created without holding the lock, the bean may look up other request scoped beans
+        let bean = create()?;
+        let mut beans = self.beans.lock().unwrap();
+        if !beans.iter().any(|scoped_bean| scoped_bean.name == name) {
+            beans.push(bean);
+        }
+        Ok(beans
+            .iter()
+            .find(|scoped_bean| scoped_bean.name == name)
+            .and_then(|scoped_bean| scoped_bean.get::<T>())
+            .cloned())
+    }
+
+    fn get<T: ?Sized + Send + Sync + 'static>(&self, name: &str) -> Option<Option<Arc<T>>> {
+        let beans = self.beans.lock().unwrap();
+        beans
+            .iter()
+            .find(|scoped_bean| scoped_bean.name == name)
+            .map(|scoped_bean| scoped_bean.get::<T>().cloned())
+    }
+}
@@ -1,44 +1,67 @@
-use crate::application_banner::{ApplicationBootBannerPrinter, Banner};
-use crate::application_listener::{
-    ApplicationListener, ApplicationStartingEventListener, BootstrapConfigFileApplicationListener,
-    DiscoveryDeRegistryApplicationListener, DiscoveryRegistryApplicationListener,
-};
+use crate::application_banner::{ApplicationBootBannerPrinter, Banner, BannerMode};
+use crate::application_builder::RustApplicationBuilder;
+use crate::application_error::{wrap, ApplicationError};
+use crate::application_listener::ApplicationListener;
 use crate::application_properties::ApplicationProperties;
 use crate::application_run_listeners::{ApplicationRunListeners, EventPublishingRunListener};
+use crate::bootstrap::bootstrap_context::BootstrapContext;
+use crate::bootstrap::bootstrap_registry::BootstrapRegistry;
 use crate::bootstrap::bootstrap_registry_initializer::BootstrapRegistryInitializer;
 use crate::bootstrap::default_bootstrap_context::DefaultBootstrapContext;
-use crate::bootstrap::initializer::ConsulBootstrapRegistryInitializer;
+use crate::cloud::client::registry::ServiceRegistry;
 use crate::context::application_event_multi_caster::ApplicationEventMultiCaster;
-use crate::env::properties::BootstrapProperties;
-use crate::initializer::{
-    ActuatorRouterInitializer, ApplicationContextInitializer,
-    ContextIdApplicationContextInitializer, ServletContextInitializer,
+use crate::diagnostics::failure_analyzer::FailureAnalyzer;
+use crate::env::command_line::command_line_property_source;
+use crate::env::configuration::DEFAULT_PROPERTY_SOURCE_NAME;
+use crate::env::origin::load_dotenv;
+use crate::env::properties::{
+    BootstrapProperties, BOOTSTRAP_LOCATION_ENV, BOOTSTRAP_LOCATION_OPTION,
+    DEFAULT_BOOTSTRAP_LOCATION,
+};
+use crate::env::refresh::ContextRefresher;
+use crate::initializer::{ApplicationContextInitializer, ServletContextInitializer};
+use crate::runner::{
+    call_runners, ApplicationArguments, ApplicationRunner, ArgumentsError, CommandLineRunner,
+    Runner,
 };
-use crate::logging::listener::{LoggingApplicationListener, LoggingCleanApplicationListener};
+use crate::shutdown_hook::ShutdownHooks;
 use crate::web::context::{ServletWebServerApplicationContext, WebServerApplicationContext};
 use crate::web_application_type::WebApplicationType;
-use application_beans::factory::bean_factory::{BeanFactory, ConfigurableBeanFactory};
+use application_beans::factory::bean_error::BeanError;
+use application_beans::factory::bean_factory::{
+    BeanFactory, ConfigurableBeanFactory, DefaultListableBeanFactory,
+};
+use application_beans::factory::component::register_components;
+use application_beans::factory::configuration_properties::bind_configuration_properties;
+use application_beans::factory::condition::{matches_all, ConditionContext};
+use application_beans::factory::condition_evaluation_report::ConditionEvaluationReport;
+use application_beans::factory::disposable_bean::DisposableBean;
 use application_context::context::application_context::{
-    ConfigurableApplicationContext, GenericApplicationContext, APPLICATION_CONTEXT,
+    ApplicationContext, ConfigurableApplicationContext, GenericApplicationContext,
+    APPLICATION_CONTEXT,
+};
+use application_core::env::environment::{
+    ApplicationEnvironment, ConfigurableEnvironment, EnvironmentCapable,
 };
-use application_core::env::environment::{ApplicationEnvironment, ConfigurableEnvironment};
 use application_core::env::property::PropertySource;
 use application_core::metrics::default_application_startup::DefaultApplicationStartup;
+use application_web::server::{is_terminating, termination_signal};
 use async_std::task::block_on;
 use async_trait::async_trait;
 use axum::http::StatusCode;
 use axum::Router;
 use clap::crate_name;
-use config::Config;
+use config::{Config, ConfigError, Value};
 use std::env::consts::OS;
 use std::error::Error;
 use std::process::Command;
 use std::sync::{Arc, OnceLock};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::spawn;
 use tokio::sync::{RwLock, RwLockReadGuard};
 use tower_http::timeout::TimeoutLayer;
 use tower_http::trace::TraceLayer;
-use tracing::{debug, info};
+use tracing::{debug, error, info};
 
 #[async_trait]
 trait Startup: Send + Sync {
@@ -80,14 +103,25 @@ impl Startup for StandardStartup {
     }
 }
 
+/// An application configured by a [`RustApplicationBuilder`], which can not be changed once built.
 pub struct RustApplication {
-    pub crate_name: String,
-    pub properties: ApplicationProperties,
-    pub bootstrap_registry_initializers: Arc<RwLock<Vec<Box<dyn BootstrapRegistryInitializer>>>>,
-    pub initializers: Arc<RwLock<Vec<Box<dyn ApplicationContextInitializer>>>>,
-    pub listeners: Arc<RwLock<Vec<Box<dyn ApplicationListener>>>>,
-    pub servlet_context_initializers: Arc<RwLock<Vec<Box<dyn ServletContextInitializer>>>>,
+    crate_name: String,
+    properties: ApplicationProperties,
+    bootstrap_registry_initializers: Vec<Box<dyn BootstrapRegistryInitializer>>,
+    initializers: Vec<Box<dyn ApplicationContextInitializer>>,
+    listeners: Vec<Box<dyn ApplicationListener>>,
+    servlet_context_initializers: Vec<Box<dyn ServletContextInitializer>>,
+    failure_analyzers: Vec<Box<dyn FailureAnalyzer>>,
+    application_runners: Vec<Box<dyn ApplicationRunner>>,
+    command_line_runners: Vec<Box<dyn CommandLineRunner>>,
+    args: Vec<String>,
+    add_command_line_properties: bool,
+    bootstrap_location: Option<String>,
+    additional_profiles: Vec<String>,
+    banner_mode: BannerMode,
+    default_properties: Vec<(String, Value)>,
     start_up: Arc<RwLock<Box<dyn Startup>>>,
+    shutdown_hooks: Arc<ShutdownHooks>,
 }
 
 static APPLICATION_RUN_LISTENERS: OnceLock<ApplicationRunListeners> = OnceLock::new();
@@ -98,53 +132,49 @@ impl Default for RustApplication {
     }
 }
 
-impl RustApplication {
-    pub fn new(crate_name: &str, application_type: WebApplicationType) -> Self {
+impl From<RustApplicationBuilder> for RustApplication {
+    fn from(builder: RustApplicationBuilder) -> Self {
         RustApplication {
-            crate_name: crate_name.to_string(),
+            crate_name: builder.crate_name,
             properties: ApplicationProperties {
-                web_application_type: application_type,
+                web_application_type: builder.web_application_type,
             },
-            bootstrap_registry_initializers: Arc::new(RwLock::new(vec![Box::new(
-                ConsulBootstrapRegistryInitializer {},
-            )])),
-            initializers: Arc::new(RwLock::new(vec![Box::new(
-                ContextIdApplicationContextInitializer {},
-            )])),
-            listeners: Arc::new(RwLock::new(vec![
-                Box::new(LoggingApplicationListener {}),
-                Box::new(ApplicationStartingEventListener {}),
-                Box::new(BootstrapConfigFileApplicationListener {}),
-                Box::new(DiscoveryRegistryApplicationListener {}),
-                Box::new(DiscoveryDeRegistryApplicationListener {}),
-                Box::new(LoggingCleanApplicationListener {}),
-            ])),
-            servlet_context_initializers: Arc::new(RwLock::new(vec![Box::new(
-                ActuatorRouterInitializer,
-            )])),
+            bootstrap_registry_initializers: builder.bootstrap_registry_initializers,
+            initializers: builder.initializers,
+            listeners: builder.listeners,
+            servlet_context_initializers: builder.servlet_context_initializers,
+            failure_analyzers: builder.failure_analyzers,
+            application_runners: builder.application_runners,
+            command_line_runners: builder.command_line_runners,
+            args: builder.args,
+            add_command_line_properties: builder.add_command_line_properties,
+            bootstrap_location: builder.bootstrap_location,
+            additional_profiles: builder.additional_profiles,
+            banner_mode: builder.banner_mode,
+            default_properties: builder.default_properties,
             start_up: Arc::new(RwLock::new(Box::new(StandardStartup {
                 start_time: 0,
                 time_taken_to_started: Default::default(),
             }))),
+            shutdown_hooks: Arc::new(builder.shutdown_hooks),
         }
     }
+}
 
-    pub async fn add_initializer(&self, initializer: Box<dyn ApplicationContextInitializer>) {
-        let mut initializers = self.initializers.write().await;
-        initializers.push(initializer);
+impl RustApplication {
+    pub fn new(crate_name: &str, application_type: WebApplicationType) -> Self {
+        RustApplicationBuilder::new(crate_name)
+            .web_application_type(application_type)
+            .build()
     }
 
-    pub async fn add_listener(&self, listener: Box<dyn ApplicationListener>) {
-        let mut listeners = self.listeners.write().await;
-        listeners.push(listener);
+    pub fn builder(crate_name: &str) -> RustApplicationBuilder {
+        RustApplicationBuilder::new(crate_name)
     }
 
-    pub async fn add_servlet_context_initializer(
-        &self,
-        initializer: Box<dyn ServletContextInitializer>,
-    ) {
-        let mut servlet_context_initializers = self.servlet_context_initializers.write().await;
-        servlet_context_initializers.push(initializer);
+    /// The application listeners, called one after the other by order.
+    pub(crate) fn get_listeners(&self) -> &[Box<dyn ApplicationListener>] {
+        &self.listeners
     }
 
     fn get_application_run_listeners(&self) -> &ApplicationRunListeners {
@@ -156,35 +186,101 @@ impl RustApplication {
         })
     }
 
-    async fn create_bootstrap_context(&self) -> DefaultBootstrapContext {
-        dotenvy::dotenv().ok();
+    async fn create_bootstrap_context(&self) -> Result<DefaultBootstrapContext, ApplicationError> {
+        load_dotenv(None);
 
         debug!("create_bootstrap_context");
 
-        let mut properties = BootstrapProperties::read_from_path("./bootstrap.toml").unwrap();
+        let location = self
+            .get_bootstrap_location()
+            .await
+            .map_err(|e| ApplicationError::Bootstrap(wrap(e)))?;
+        let mut properties = match location {
+            Some(location) => {
+                // a .env file next to the bootstrap file, the variables set already are kept
+                if let Some(file) = BootstrapProperties::find_file(&location) {
+                    if let Some(directory) = file.parent() {
+                        load_dotenv(Some(&directory.join(".env")));
+                    }
+                }
+                BootstrapProperties::read_from_required_path(&location)
+                    .map_err(|e| ApplicationError::Bootstrap(wrap(e)))?
+            }
+            None => BootstrapProperties::read_from_path(DEFAULT_BOOTSTRAP_LOCATION)
+                .map_err(|e| ApplicationError::Bootstrap(wrap(e)))?,
+        };
         if properties.application.name.is_empty() {
             properties.application.name = self.crate_name.clone();
         }
         let context = DefaultBootstrapContext::new(properties);
-        let initializers = self.bootstrap_registry_initializers.read().await;
-        let initializers = initializers.iter();
+        // the application context does not exist yet, conditions see the bootstrap properties only
+        let environment = self.create_bootstrap_environment(context.get_bootstrap_properties());
+        let bean_factory = DefaultListableBeanFactory::default();
+        let condition_context = ConditionContext {
+            environment: &environment,
+            bean_factory: &bean_factory,
+        };
+        let initializers = self.bootstrap_registry_initializers.iter();
         for initializer in initializers {
-            initializer.initial(&context);
+            if matches_all(
+                initializer.get_name(),
+                &initializer.conditions(),
+                &condition_context,
+            ) {
+                initializer
+                    .initial(&context)
+                    .map_err(|e| ApplicationError::Bootstrap(wrap(e)))?;
+            }
         }
+        context.register(ConditionEvaluationReport::get(&bean_factory));
 
-        context
+        Ok(context)
+    }
+
+    /// The location set by the `--bootstrap` option, the `BOOTSTRAP_LOCATION` environment
+    /// variable or the builder, in this order.
+    async fn get_bootstrap_location(&self) -> Result<Option<String>, ArgumentsError> {
+        let args = self.get_application_arguments()?;
+        if let Some(location) = args
+            .get_option_values(BOOTSTRAP_LOCATION_OPTION)
+            .and_then(|values| values.last())
+        {
+            return Ok(Some(location.clone()));
+        }
+        if let Ok(location) = std::env::var(BOOTSTRAP_LOCATION_ENV) {
+            return Ok(Some(location));
+        }
+        Ok(self.bootstrap_location.clone())
+    }
+
+    /// The arguments are only rejected when they are added to the environment, an application
+    /// without command line properties may take arguments its runners parse on their own.
+    fn get_application_arguments(&self) -> Result<ApplicationArguments, ArgumentsError> {
+        if self.add_command_line_properties {
+            ApplicationArguments::new(self.args.clone())
+        } else {
+            Ok(ApplicationArguments::lenient(self.args.clone()))
+        }
+    }
+
+    fn create_bootstrap_environment(
+        &self,
+        bootstrap_properties: &BootstrapProperties,
+    ) -> ApplicationEnvironment {
+        let mut environment =
+            ApplicationEnvironment::new(self.get_active_profiles(bootstrap_properties), None, None);
+        environment.add_property_source(PropertySource {
+            name: "bootstrapProperties".to_string(),
+            source: Config::try_from(bootstrap_properties).unwrap_or_default(),
+        });
+        environment
     }
 
     fn create_environment(
         &self,
         bootstrap_properties: &BootstrapProperties,
-    ) -> Result<ApplicationEnvironment, Box<dyn Error>> {
-        let active_profiles = bootstrap_properties
-            .application
-            .config
-            .activate
-            .profiles
-            .clone();
+    ) -> Result<ApplicationEnvironment, Box<dyn Error + Send + Sync>> {
+        let active_profiles = self.get_active_profiles(bootstrap_properties);
         let locations = bootstrap_properties.application.config.locations.clone();
         let search_file_names = bootstrap_properties.application.config.file_names.clone();
         Ok(ApplicationEnvironment::new(
@@ -194,22 +290,55 @@ impl RustApplication {
         ))
     }
 
+    /// The profiles of the bootstrap properties followed by the additional profiles.
+    fn get_active_profiles(&self, bootstrap_properties: &BootstrapProperties) -> Vec<String> {
+        let mut active_profiles = bootstrap_properties
+            .application
+            .config
+            .activate
+            .profiles
+            .clone();
+        for profile in &self.additional_profiles {
+            if !active_profiles.contains(profile) {
+                active_profiles.push(profile.clone());
+            }
+        }
+        active_profiles
+    }
+
     async fn prepare_environment(
         &self,
         bootstrap_context: &DefaultBootstrapContext,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), ApplicationError> {
         debug!("prepare_environment");
         {
             let bootstrap_properties = bootstrap_context.get_bootstrap_properties();
-            let mut environment = self.create_environment(bootstrap_properties)?;
-            environment = self.configure_environment(environment, bootstrap_properties);
+            let mut environment = self
+                .create_environment(bootstrap_properties)
+                .map_err(|e| ApplicationError::Environment(wrap(e)))?;
+            environment = self
+                .configure_environment(environment, bootstrap_properties)
+                .map_err(|e| ApplicationError::Environment(wrap(e)))?;
+            if self.add_command_line_properties {
+                let args = self
+                    .get_application_arguments()
+                    .map_err(|e| ApplicationError::Environment(wrap(e)))?;
+                if !args.get_option_names().is_empty() {
+                    let property_source = command_line_property_source(&args)
+                        .map_err(|e| ApplicationError::Environment(wrap(e)))?;
+                    environment
+                        .get_property_sources_mut()
+                        .add_first(property_source);
+                }
+            }
             let application_context = self.get_application_context().await;
             application_context.set_environment(environment).await;
         }
         let listeners = self.get_application_run_listeners();
         listeners
             .environment_prepared(self, bootstrap_context)
-            .await;
+            .await
+            .map_err(|e| ApplicationError::Environment(wrap(e)))?;
         Ok(())
     }
 
@@ -217,78 +346,67 @@ impl RustApplication {
         &self,
         environment: ApplicationEnvironment,
         bootstrap_properties: &BootstrapProperties,
-    ) -> ApplicationEnvironment {
+    ) -> Result<ApplicationEnvironment, ConfigError> {
         let mut builder = Config::builder();
-        builder = builder
-            .set_default(
-                "application.name",
-                bootstrap_properties.get_application_name(),
-            )
-            .unwrap();
-        builder = builder
-            .set_default(
-                "application.port",
-                bootstrap_properties.get_application_port(),
-            )
-            .unwrap();
+        builder = builder.set_default(
+            "application.name",
+            bootstrap_properties.get_application_name(),
+        )?;
+        builder = builder.set_default(
+            "application.port",
+            bootstrap_properties.get_application_port(),
+        )?;
 
         if let Some(cloud) = &bootstrap_properties.application.cloud {
             if let Some(discovery) = &cloud.discovery {
-                builder = builder
-                    .set_default(
-                        "application.cloud.discovery.server.address",
-                        discovery.server.address.clone(),
-                    )
-                    .unwrap();
-                builder = builder
-                    .set_default(
-                        "application.cloud.discovery.server.token",
-                        discovery.server.token.clone(),
-                    )
-                    .unwrap();
+                builder = builder.set_default(
+                    "application.cloud.discovery.server.address",
+                    discovery.server.address.clone(),
+                )?;
+                builder = builder.set_default(
+                    "application.cloud.discovery.server.token",
+                    discovery.server.token.clone(),
+                )?;
                 if let Some(health) = &discovery.health {
-                    builder = builder
-                        .set_default(
-                            "application.cloud.discovery.health.check.path",
-                            health.check.path.clone(),
-                        )
-                        .unwrap();
-                    builder = builder
-                        .set_default(
-                            "application.cloud.discovery.health.check.interval",
-                            health.check.interval.clone(),
-                        )
-                        .unwrap();
+                    builder = builder.set_default(
+                        "application.cloud.discovery.health.check.path",
+                        health.check.path.clone(),
+                    )?;
+                    builder = builder.set_default(
+                        "application.cloud.discovery.health.check.interval",
+                        health.check.interval.clone(),
+                    )?;
                 }
             }
             if let Some(config) = &cloud.config {
+                builder =
+                    builder.set_default("application.cloud.config.enabled", config.enabled)?;
                 builder = builder
-                    .set_default("application.cloud.config.enabled", config.enabled)
-                    .unwrap();
-                builder = builder
-                    .set_default("application.cloud.config.address", config.address.clone())
-                    .unwrap();
-                builder = builder
-                    .set_default("application.cloud.config.token", config.token.clone())
-                    .unwrap();
+                    .set_default("application.cloud.config.address", config.address.clone())?;
+                builder =
+                    builder.set_default("application.cloud.config.token", config.token.clone())?;
             }
         }
 
+        for (key, value) in &self.default_properties {
+            builder = builder.set_default(key.as_str(), value.clone())?;
+        }
+
         let mut env = environment;
         env.add_property_source(PropertySource {
-            name: "defaultProperties".to_string(),
-            source: builder.build().unwrap(),
+            name: DEFAULT_PROPERTY_SOURCE_NAME.to_string(),
+            source: builder.build()?,
         });
 
-        env
+        Ok(env)
     }
 
     pub fn create_application_context(&self) {
         debug!("create_application_context");
         let application_type = self.properties.web_application_type;
-        let context: Box<dyn ConfigurableApplicationContext> = match application_type {
-            WebApplicationType::NONE => Box::new(GenericApplicationContext::default()),
-            WebApplicationType::WEB => Box::new(ServletWebServerApplicationContext::default()),
+        let context: Arc<dyn ConfigurableApplicationContext> = match application_type {
+            WebApplicationType::NONE => Arc::new(GenericApplicationContext::default()),
+            WebApplicationType::WEB => Arc::new(ServletWebServerApplicationContext::default()),
         };
 
         let mut application_context_write = block_on(APPLICATION_CONTEXT.write());
@@ -297,42 +415,86 @@ impl RustApplication {
 
     async fn prepare_context(
         &self,
-        bootstrap_context: DefaultBootstrapContext,
-    ) -> Result<(), Box<dyn Error>> {
+        bootstrap_context: &DefaultBootstrapContext,
+    ) -> Result<(), ApplicationError> {
         debug!("prepare_context");
-        {
-            let application_context = self.get_application_context().await;
-            application_context
-                .get_bean_factory()
-                .set(bootstrap_context);
-
-            self.apply_initializers(&application_context).await;
-        }
         let application_context = self.get_application_context().await;
-        let bootstrap_context = application_context
-            .get_bean_factory()
-            .get::<DefaultBootstrapContext>();
+        let bean_factory = application_context.get_bean_factory();
+        if let Some(report) = bootstrap_context.get::<Arc<ConditionEvaluationReport>>() {
+            bean_factory
+                .set_as(ConditionEvaluationReport::BEAN_NAME, report.clone())
+                .map_err(|e| ApplicationError::Context(wrap(e)))?;
+        }
+        let args = self
+            .get_application_arguments()
+            .map_err(|e| ApplicationError::Context(wrap(e)))?;
+        bean_factory
+            .set(args)
+            .map_err(|e| ApplicationError::Context(wrap(e)))?;
+        self.apply_initializers(&application_context).await;
+        // initializers may have registered their own registry, e.g. a mock in tests
+        if let Some(registry) = bootstrap_context.get::<Arc<dyn ServiceRegistry>>() {
+            if bean_factory.try_get::<dyn ServiceRegistry>().is_none() {
+                bean_factory
+                    .set_as::<dyn ServiceRegistry>("serviceRegistry", registry.clone())
+                    .map_err(|e| ApplicationError::Context(wrap(e)))?;
+            }
+        }
+        if let Some(refresher) = bootstrap_context.get::<Arc<ContextRefresher>>() {
+            bean_factory
+                .set_as(ContextRefresher::BEAN_NAME, refresher.clone())
+                .map_err(|e| ApplicationError::Context(wrap(e)))?;
+            // stops the watchers when the context is closed
+            bean_factory
+                .set_as::<dyn DisposableBean>(ContextRefresher::BEAN_NAME, refresher.clone())
+                .map_err(|e| ApplicationError::Context(wrap(e)))?;
+        }
         let listeners = self.get_application_run_listeners();
-        listeners.context_prepared(self, &bootstrap_context).await;
+        listeners
+            .context_prepared(self, bootstrap_context)
+            .await
+            .map_err(|e| ApplicationError::Context(wrap(e)))?;
 
-        self.load();
+        self.load(bean_factory)
+            .map_err(|e| ApplicationError::Context(wrap(e)))?;
+        {
+            let environment = application_context.get_environment().await;
+            bind_configuration_properties(&environment, bean_factory)
+                .map_err(|e| ApplicationError::Context(wrap(e)))?;
+        }
 
-        listeners.context_loaded(self, &bootstrap_context).await;
+        listeners
+            .context_loaded(self, bootstrap_context)
+            .await
+            .map_err(|e| ApplicationError::Context(wrap(e)))?;
 
         Ok(())
     }
 
-    async fn refresh_context(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+    async fn refresh_context(&self) -> Result<(), ApplicationError> {
         debug!("refresh_context");
 
         let application_context = self.get_application_context().await;
 
-        application_context.refresh().await;
+        application_context
+            .refresh()
+            .await
+            .map_err(|e| ApplicationError::Refresh(wrap(e)))?;
+
+        let report = ConditionEvaluationReport::get(application_context.get_bean_factory());
+        debug!("{}", report);
 
         Ok(())
     }
 
-    async fn after_refresh(&self) {
+    async fn close_context(&self) {
+        let application_context = self.get_application_context().await;
+        if let Err(e) = application_context.close().await {
+            info!("Close application context failed, {}", e);
+        }
+    }
+
+    async fn after_refresh(&self) -> Result<(), ApplicationError> {
         let application_context = self.get_application_context().await;
         application_context.after_refresh().await;
         let application_type = self.properties.web_application_type;
@@ -346,16 +508,22 @@ impl RustApplication {
                     self.crate_name,
                     start_up.get_process_up_time().await
                 );
-                self.started().await
+                self.started()
+                    .await
+                    .map_err(|e| ApplicationError::Started(wrap(e)))?;
+                self.call_runners().await?;
             }
             WebApplicationType::WEB => {
                 let application_context = application_context
                     .as_any()
                     .downcast_ref::<ServletWebServerApplicationContext>()
-                    .unwrap();
+                    .ok_or_else(|| {
+                        ApplicationError::WebServer(wrap(
+                            "The application context is not a servlet web server application context",
+                        ))
+                    })?;
                 let web_server = application_context.get_web_server().await;
-                let servlet_context_initializers = self.servlet_context_initializers.read().await;
-                let servlet_context_initializers = servlet_context_initializers.iter();
+                let servlet_context_initializers = self.servlet_context_initializers.iter();
                 // route
                 let mut router = Router::new().layer((
                     TraceLayer::new_for_http(),
@@ -368,9 +536,25 @@ impl RustApplication {
                 ));
 
                 for initializer in servlet_context_initializers {
-                    router = initializer.initialize(router);
+                    let matched = {
+                        let environment = application_context.get_environment().await;
+                        let condition_context = ConditionContext {
+                            environment: &environment,
+                            bean_factory: application_context.get_bean_factory(),
+                        };
+                        matches_all(
+                            initializer.get_name(),
+                            &initializer.conditions(),
+                            &condition_context,
+                        )
+                    };
+                    if matched {
+                        router = initializer.initialize(router);
+                    }
                 }
-                let condvar_pair = web_server.start(router).unwrap();
+                let condvar_pair = web_server
+                    .start(router)
+                    .map_err(|e| ApplicationError::WebServer(wrap(e)))?;
                 let start_up = self.start_up.read().await;
                 start_up.started().await;
                 info!(
@@ -378,7 +562,17 @@ impl RustApplication {
                     self.crate_name,
                     start_up.get_process_up_time().await
                 );
-                self.started().await;
+                let result = match self.started().await {
+                    Ok(_) => self.call_runners().await,
+                    Err(e) => Err(ApplicationError::Started(wrap(e))),
+                };
+                if let Err(e) = result {
+                    // nothing waits for the server to stop anymore
+                    if let Err(stop_error) = web_server.stop().await {
+                        error!("Stop web server failed, {}", stop_error);
+                    }
+                    return Err(e);
+                }
                 // 等待线程启动。
                 let (lock, cvar) = &*condvar_pair;
                 let mut stopped = lock.lock().unwrap();
@@ -388,28 +582,66 @@ impl RustApplication {
                 }
             }
         }
+        Ok(())
     }
 
     async fn apply_initializers(
         &self,
-        application_context: &Box<dyn ConfigurableApplicationContext>,
+        application_context: &Arc<dyn ConfigurableApplicationContext>,
     ) {
-        let initializers = &self.initializers.read().await;
-        let initializers = initializers.iter();
+        let initializers = self.initializers.iter();
         for initializer in initializers {
-            initializer.initialize(&application_context);
+            let matched = {
+                let environment = application_context.get_environment().await;
+                let condition_context = ConditionContext {
+                    environment: &environment,
+                    bean_factory: application_context.get_bean_factory(),
+                };
+                matches_all(
+                    initializer.get_name(),
+                    &initializer.conditions(),
+                    &condition_context,
+                )
+            };
+            if matched {
+                initializer.initialize(&application_context);
+            }
         }
     }
 
-    fn load(&self) {}
+    /// Run the registered and the submitted runners by order, after the started listeners.
+    async fn call_runners(&self) -> Result<(), ApplicationError> {
+        let application_context = self.get_application_context().await.clone();
+        let args = application_context
+            .get_bean_factory()
+            .get::<ApplicationArguments>();
+        let runners = self
+            .application_runners
+            .iter()
+            .map(|runner| Runner::Application(runner.as_ref()))
+            .chain(
+                self.command_line_runners
+                    .iter()
+                    .map(|runner| Runner::CommandLine(runner.as_ref())),
+            )
+            .collect();
+        call_runners(runners, &application_context, args)
+            .await
+            .map_err(|e| ApplicationError::Runner(wrap(e)))
+    }
+
+    /// Register the bean definitions of the components.
+    fn load(&self, bean_factory: &DefaultListableBeanFactory) -> Result<(), BeanError> {
+        register_components(bean_factory)
+    }
 
-    pub async fn started(&self) {
+    pub async fn started(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
         let listeners = self.get_application_run_listeners();
         let application_context = self.get_application_context().await;
         let bootstrap_context = application_context
             .get_bean_factory()
             .get::<DefaultBootstrapContext>();
-        listeners.started(self, &bootstrap_context).await;
+        listeners.started(self, &bootstrap_context).await
     }
 
     async fn stopped(&self) {
@@ -418,16 +650,58 @@ impl RustApplication {
         let bootstrap_context = application_context
             .get_bean_factory()
             .get::<DefaultBootstrapContext>();
-        listeners.stopped(self, &bootstrap_context).await;
+        if let Err(e) = listeners.stopped(self, &bootstrap_context).await {
+            info!("{}", e);
+        }
     }
 
-    async fn failed(&self) {
+    async fn failed(&self, error: &ApplicationError) {
         let listeners = self.get_application_run_listeners();
         let application_context = self.get_application_context().await;
         let bootstrap_context = application_context
             .get_bean_factory()
             .get::<DefaultBootstrapContext>();
-        listeners.failed(self, &bootstrap_context).await;
+        if let Err(e) = listeners.failed(self, &bootstrap_context, error).await {
+            info!("{}", e);
+        }
+    }
+
+    /// Run the startup steps, up to the stop of the web server for a web application.
+    async fn start(&self) -> Result<(), ApplicationError> {
+        let application_context = self.get_application_context().await.clone();
+        let bootstrap_context = application_context
+            .get_bean_factory()
+            .get::<DefaultBootstrapContext>();
+
+        let listeners = self.get_application_run_listeners();
+        listeners
+            .starting(self, bootstrap_context)
+            .await
+            .map_err(|e| ApplicationError::Bootstrap(wrap(e)))?;
+
+        self.prepare_environment(bootstrap_context).await?;
+
+        let banner = ApplicationBootBannerPrinter {
+            mode: self.banner_mode,
+        };
+        banner.print();
+
+        self.prepare_context(bootstrap_context).await?;
+
+        self.refresh_context().await?;
+        self.after_refresh().await
+    }
+
+    /// Log the analysis of `error` if an analyzer recognizes it, the error itself otherwise.
+    async fn report_failure(&self, error: &ApplicationError) {
+        match self
+            .failure_analyzers
+            .iter()
+            .find_map(|analyzer| analyzer.analyze(error))
+        {
+            Some(analysis) => error!("{}", analysis),
+            None => error!("Application start failed, {}", error),
+        }
     }
 
     async fn set_start_up(&self, start_up: StandardStartup) {
@@ -438,7 +712,8 @@ impl RustApplication {
 
 #[async_trait]
 pub trait Application {
-    async fn run(&self) -> Result<(), Box<dyn Error>>;
+    /// Start the application and wait for it to stop, `Err` if it failed to start.
+    async fn run(&self) -> Result<(), ApplicationError>;
 
     fn stop(&self, application_name: &str) -> Result<(), Box<dyn Error>> {
         match OS {
@@ -492,53 +767,86 @@ pub trait Application {
 
     async fn get_application_context(
         &self,
-    ) -> RwLockReadGuard<'_, Box<dyn ConfigurableApplicationContext>> {
+    ) -> RwLockReadGuard<'_, Arc<dyn ConfigurableApplicationContext>> {
         APPLICATION_CONTEXT.read().await
     }
 
     async fn get_application_context_blocking(
         &self,
-    ) -> RwLockReadGuard<'_, Box<dyn ConfigurableApplicationContext>> {
+    ) -> RwLockReadGuard<'_, Arc<dyn ConfigurableApplicationContext>> {
         block_on(APPLICATION_CONTEXT.read())
     }
 }
 
 #[async_trait]
 impl Application for RustApplication {
-    async fn run(&self) -> Result<(), Box<dyn Error>> {
-        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+    async fn run(&self) -> Result<(), ApplicationError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| ApplicationError::Bootstrap(wrap(e)))?;
         let start_up = StandardStartup {
             start_time: now.as_millis(),
             time_taken_to_started: Default::default(),
         };
         self.set_start_up(start_up).await;
 
-        let bootstrap_context = self.create_bootstrap_context().await;
-
-        let listeners = self.get_application_run_listeners();
-        listeners.starting(self, &bootstrap_context).await;
+        // without bootstrap context there are no listeners to notify
+        let bootstrap_context = match self.create_bootstrap_context().await {
+            Ok(bootstrap_context) => bootstrap_context,
+            Err(e) => {
+                self.report_failure(&e).await;
+                return Err(e);
+            }
+        };
 
         self.create_application_context();
+        {
+            // registered first so that the failed listeners find it whatever step fails
+            let application_context = self.get_application_context().await;
+            application_context
+                .get_bean_factory()
+                .set(bootstrap_context)
+                .map_err(|e| ApplicationError::Bootstrap(wrap(e)))?;
+        }
 
-        self.prepare_environment(&bootstrap_context).await?;
-
-        let banner = ApplicationBootBannerPrinter {};
-        banner.print();
+        // the hooks run on the termination signal, before the context closes
+        let shutdown_hooks = self.shutdown_hooks.clone();
+        let shutdown_hooks = spawn(async move {
+            termination_signal().await;
+            shutdown_hooks.run().await;
+        });
 
-        self.prepare_context(bootstrap_context).await?;
+        let result = match self.properties.web_application_type {
+            // the web server stops on the termination signal
+            WebApplicationType::WEB => self.start().await,
+            WebApplicationType::NONE => {
+                tokio::select! {
+                    result = self.start() => result,
+                    _ = termination_signal() => {
+                        info!("Received termination signal, stopping the application");
+                        Ok(())
+                    }
+                }
+            }
+        };
 
-        let result = self.refresh_context().await;
+        if is_terminating() {
+            let _ = shutdown_hooks.await;
+        } else {
+            shutdown_hooks.abort();
+        }
 
         match result {
             Ok(_) => {
-                self.after_refresh().await;
+                self.close_context().await;
                 self.stopped().await;
                 Ok(())
             }
             Err(e) => {
-                info!("Application start failed {:?}", e);
-                self.failed().await;
-                Ok(())
+                self.report_failure(&e).await;
+                self.close_context().await;
+                self.failed(&e).await;
+                Err(e)
             }
         }
     }
@@ -10,8 +10,17 @@ _|"""""|_|"""""|_|"""""|_|"""""|_|"""""|_|"""""|_|"""""|_|"""""|_|"""""|_|"""""|
 "`-0-0-'"`-0-0-'"`-0-0-'"`-0-0-'"`-0-0-'"`-0-0-'"`-0-0-'"`-0-0-'"`-0-0-'"`-0-0-'"`-0-0-'./o--000'"`-0-0-'"`-0-0-'"`-0-0-'"`-0-0-'
 "###;
 
-pub struct ApplicationBootBannerPrinter {
+/// Where the banner is printed at startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BannerMode {
+    Off,
+    Console,
+    #[default]
+    Log,
+}
 
+pub struct ApplicationBootBannerPrinter {
+    pub mode: BannerMode,
 }
 
 pub trait Banner {
@@ -20,6 +29,10 @@ pub trait Banner {
 
 impl Banner for ApplicationBootBannerPrinter {
     fn print(&self) {
-        info!("{}", BANNER);
+        match self.mode {
+            BannerMode::Off => {}
+            BannerMode::Console => println!("{}", BANNER),
+            BannerMode::Log => info!("{}", BANNER),
+        }
     }
 }
@@ -0,0 +1,293 @@
+use crate::application::RustApplication;
+use crate::application_banner::BannerMode;
+use crate::application_listener::{
+    ApplicationListener, ApplicationStartingEventListener, BootstrapConfigFileApplicationListener,
+    DiscoveryDeRegistryApplicationListener, DiscoveryRegistryApplicationListener,
+    RefreshApplicationListener,
+};
+use crate::bootstrap::bootstrap_registry_initializer::BootstrapRegistryInitializer;
+use crate::bootstrap::initializer::{
+    ConsulBootstrapRegistryInitializer, RefreshBootstrapRegistryInitializer,
+};
+use crate::diagnostics::analyzer::{
+    BindErrorFailureAnalyzer, BootstrapFileNotFoundFailureAnalyzer, ConfigErrorFailureAnalyzer,
+    DiscoveryFailureAnalyzer, PortInUseFailureAnalyzer,
+};
+use crate::diagnostics::failure_analyzer::FailureAnalyzer;
+use crate::initializer::{
+    ActuatorRouterInitializer, ApplicationContextInitializer,
+    ContextIdApplicationContextInitializer, RefreshEndpointRouterInitializer,
+    ServletContextInitializer,
+};
+use crate::logging::listener::{LoggingApplicationListener, LoggingCleanApplicationListener};
+use crate::runner::{ApplicationRunner, CommandLineRunner};
+use crate::shutdown_hook::ShutdownHooks;
+use crate::web_application_type::WebApplicationType;
+use config::Value;
+use std::any::type_name;
+use std::future::Future;
+use std::time::Duration;
+
+/// Configures a [`RustApplication`], starting from the built-in listeners and initializers.
+///
+/// # Examples
+/// ```ignore
+/// let application = RustApplication::builder(crate_name!())
+///     .web_application_type(WebApplicationType::NONE)
+///     .bootstrap_location("./config/bootstrap.toml")
+///     .additional_profile("batch")
+///     .banner_mode(BannerMode::Off)
+///     .default_property("application.port", 9090)
+///     .without_bootstrap_registry_initializer::<ConsulBootstrapRegistryInitializer>()
+///     .listener(Box::new(ApplicationStartedEventListener {}))
+///     .build();
+/// application.run().await?;
+/// ```
+pub struct RustApplicationBuilder {
+    pub(crate) crate_name: String,
+    pub(crate) web_application_type: WebApplicationType,
+    pub(crate) bootstrap_location: Option<String>,
+    pub(crate) additional_profiles: Vec<String>,
+    pub(crate) banner_mode: BannerMode,
+    pub(crate) default_properties: Vec<(String, Value)>,
+    pub(crate) args: Vec<String>,
+    pub(crate) add_command_line_properties: bool,
+    pub(crate) bootstrap_registry_initializers: Vec<Box<dyn BootstrapRegistryInitializer>>,
+    pub(crate) initializers: Vec<Box<dyn ApplicationContextInitializer>>,
+    pub(crate) listeners: Vec<Box<dyn ApplicationListener>>,
+    pub(crate) servlet_context_initializers: Vec<Box<dyn ServletContextInitializer>>,
+    pub(crate) failure_analyzers: Vec<Box<dyn FailureAnalyzer>>,
+    pub(crate) application_runners: Vec<Box<dyn ApplicationRunner>>,
+    pub(crate) command_line_runners: Vec<Box<dyn CommandLineRunner>>,
+    pub(crate) shutdown_hooks: ShutdownHooks,
+}
+
+impl RustApplicationBuilder {
+    pub fn new(crate_name: &str) -> Self {
+        RustApplicationBuilder {
+            crate_name: crate_name.to_string(),
+            web_application_type: WebApplicationType::WEB,
+            bootstrap_location: None,
+            additional_profiles: vec![],
+            banner_mode: BannerMode::default(),
+            default_properties: vec![],
+            args: std::env::args().skip(1).collect(),
+            add_command_line_properties: true,
+            bootstrap_registry_initializers: vec![
+                Box::new(ConsulBootstrapRegistryInitializer {}),
+                Box::new(RefreshBootstrapRegistryInitializer {}),
+            ],
+            initializers: vec![Box::new(ContextIdApplicationContextInitializer {})],
+            listeners: vec![
+                Box::new(LoggingApplicationListener {}),
+                Box::new(ApplicationStartingEventListener {}),
+                Box::new(BootstrapConfigFileApplicationListener {}),
+                Box::new(DiscoveryRegistryApplicationListener {}),
+                Box::new(DiscoveryDeRegistryApplicationListener {}),
+                Box::new(RefreshApplicationListener {}),
+                Box::new(LoggingCleanApplicationListener {}),
+            ],
+            servlet_context_initializers: vec![
+                Box::new(ActuatorRouterInitializer),
+                Box::new(RefreshEndpointRouterInitializer),
+            ],
+            failure_analyzers: vec![
+                Box::new(BootstrapFileNotFoundFailureAnalyzer),
+                Box::new(PortInUseFailureAnalyzer),
+                Box::new(ConfigErrorFailureAnalyzer),
+                Box::new(BindErrorFailureAnalyzer),
+                Box::new(DiscoveryFailureAnalyzer),
+            ],
+            application_runners: vec![],
+            command_line_runners: vec![],
+            shutdown_hooks: ShutdownHooks::default(),
+        }
+    }
+
+    pub fn web_application_type(mut self, web_application_type: WebApplicationType) -> Self {
+        self.web_application_type = web_application_type;
+        self
+    }
+
+    /// Read the bootstrap properties from the file or directory `location`, which must exist,
+    /// instead of `./bootstrap.toml`. The `--bootstrap <path>` option and the
+    /// `BOOTSTRAP_LOCATION` environment variable take precedence.
+    pub fn bootstrap_location(mut self, location: &str) -> Self {
+        self.bootstrap_location = Some(location.to_string());
+        self
+    }
+
+    /// Activate `profile` in addition to the profiles of the bootstrap properties.
+    pub fn additional_profile(mut self, profile: &str) -> Self {
+        self.additional_profiles.push(profile.to_string());
+        self
+    }
+
+    pub fn banner_mode(mut self, banner_mode: BannerMode) -> Self {
+        self.banner_mode = banner_mode;
+        self
+    }
+
+    /// Set a property used when no other property source sets `key`.
+    pub fn default_property(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.default_properties
+            .push((key.to_string(), value.into()));
+        self
+    }
+
+    /// Replace the arguments of the process, without the program name.
+    pub fn args(mut self, args: Vec<String>) -> Self {
+        self.args = args;
+        self
+    }
+
+    /// Whether the `--name=value` and `--name value` options override the properties of the environment,
+    /// `true` by default. Arguments that can not be parsed as options, e.g. `--offset -1`, fail the
+    /// application only when the options are added, they are non-option arguments otherwise.
+    pub fn add_command_line_properties(mut self, add_command_line_properties: bool) -> Self {
+        self.add_command_line_properties = add_command_line_properties;
+        self
+    }
+
+    pub fn bootstrap_registry_initializer(
+        mut self,
+        initializer: Box<dyn BootstrapRegistryInitializer>,
+    ) -> Self {
+        self.bootstrap_registry_initializers.push(initializer);
+        self
+    }
+
+    pub fn initializer(mut self, initializer: Box<dyn ApplicationContextInitializer>) -> Self {
+        self.initializers.push(initializer);
+        self
+    }
+
+    pub fn listener(mut self, listener: Box<dyn ApplicationListener>) -> Self {
+        self.listeners.push(listener);
+        self
+    }
+
+    pub fn servlet_context_initializer(
+        mut self,
+        initializer: Box<dyn ServletContextInitializer>,
+    ) -> Self {
+        self.servlet_context_initializers.push(initializer);
+        self
+    }
+
+    pub fn failure_analyzer(mut self, analyzer: Box<dyn FailureAnalyzer>) -> Self {
+        self.failure_analyzers.push(analyzer);
+        self
+    }
+
+    pub fn application_runner(mut self, runner: Box<dyn ApplicationRunner>) -> Self {
+        self.application_runners.push(runner);
+        self
+    }
+
+    pub fn command_line_runner(mut self, runner: Box<dyn CommandLineRunner>) -> Self {
+        self.command_line_runners.push(runner);
+        self
+    }
+
+    /// Run `hook` when the application receives SIGTERM or Ctrl-C, before the application
+    /// context is closed. The runners of an application without web server are stopped.
+    pub fn shutdown_hook<F, Fut>(mut self, hook: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.shutdown_hooks.add(hook);
+        self
+    }
+
+    /// Set the time all the shutdown hooks together are given to complete, 30 seconds by default.
+    pub fn shutdown_timeout(mut self, shutdown_timeout: Duration) -> Self {
+        self.shutdown_hooks.set_timeout(shutdown_timeout);
+        self
+    }
+
+    /// Remove the bootstrap registry initializers of type `T`, e.g. the Consul one.
+    pub fn without_bootstrap_registry_initializer<T: BootstrapRegistryInitializer>(
+        mut self,
+    ) -> Self {
+        self.bootstrap_registry_initializers
+            .retain(|initializer| initializer.get_name() != type_name::<T>());
+        self
+    }
+
+    /// Remove the application context initializers of type `T`.
+    pub fn without_initializer<T: ApplicationContextInitializer>(mut self) -> Self {
+        self.initializers
+            .retain(|initializer| initializer.get_name() != type_name::<T>());
+        self
+    }
+
+    /// Remove the listeners of type `T`, e.g. the discovery registration.
+    pub fn without_listener<T: ApplicationListener>(mut self) -> Self {
+        self.listeners
+            .retain(|listener| listener.type_name() != type_name::<T>());
+        self
+    }
+
+    /// Remove the servlet context initializers of type `T`, e.g. the actuator routes.
+    pub fn without_servlet_context_initializer<T: ServletContextInitializer>(mut self) -> Self {
+        self.servlet_context_initializers
+            .retain(|initializer| initializer.get_name() != type_name::<T>());
+        self
+    }
+
+    pub fn build(self) -> RustApplication {
+        RustApplication::from(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use application_context::context::application_context::ConfigurableApplicationContext;
+    use std::sync::Arc;
+
+    struct NamingInitializer;
+
+    impl ApplicationContextInitializer for NamingInitializer {
+        fn initialize(&self, _application_context: &Arc<dyn ConfigurableApplicationContext>) {}
+    }
+
+    #[test]
+    fn without_listener_removes_only_the_listeners_of_the_type() {
+        let builder = RustApplicationBuilder::new("test")
+            .without_listener::<DiscoveryRegistryApplicationListener>();
+
+        let listeners: Vec<&str> = builder
+            .listeners
+            .iter()
+            .map(|listener| listener.type_name())
+            .collect();
+        assert_eq!(
+            listeners,
+            vec![
+                type_name::<LoggingApplicationListener>(),
+                type_name::<ApplicationStartingEventListener>(),
+                type_name::<BootstrapConfigFileApplicationListener>(),
+                type_name::<DiscoveryDeRegistryApplicationListener>(),
+                type_name::<RefreshApplicationListener>(),
+                type_name::<LoggingCleanApplicationListener>(),
+            ]
+        );
+    }
+
+    #[test]
+    fn without_initializer_removes_only_the_initializers_of_the_type() {
+        let builder = RustApplicationBuilder::new("test")
+            .initializer(Box::new(NamingInitializer))
+            .without_initializer::<ContextIdApplicationContextInitializer>();
+
+        let initializers: Vec<&str> = builder
+            .initializers
+            .iter()
+            .map(|initializer| initializer.get_name())
+            .collect();
+        assert_eq!(initializers, vec![type_name::<NamingInitializer>()]);
+    }
+}
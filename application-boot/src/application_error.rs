@@ -0,0 +1,65 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+
+/// Why the application failed to start, by the startup step that failed.
+#[derive(Debug, Clone)]
+pub enum ApplicationError {
+    /// The bootstrap properties could not be read or a starting listener failed.
+    Bootstrap(Arc<dyn Error + Send + Sync>),
+    /// The environment could not be prepared.
+    Environment(Arc<dyn Error + Send + Sync>),
+    /// The application context could not be prepared or its components loaded.
+    Context(Arc<dyn Error + Send + Sync>),
+    /// The application context could not be refreshed.
+    Refresh(Arc<dyn Error + Send + Sync>),
+    /// The web server could not be started.
+    WebServer(Arc<dyn Error + Send + Sync>),
+    /// A started listener failed.
+    Started(Arc<dyn Error + Send + Sync>),
+    /// An application or command line runner failed.
+    Runner(Arc<dyn Error + Send + Sync>),
+}
+
+impl ApplicationError {
+    pub fn cause(&self) -> &Arc<dyn Error + Send + Sync> {
+        match self {
+            ApplicationError::Bootstrap(cause)
+            | ApplicationError::Environment(cause)
+            | ApplicationError::Context(cause)
+            | ApplicationError::Refresh(cause)
+            | ApplicationError::WebServer(cause)
+            | ApplicationError::Started(cause)
+            | ApplicationError::Runner(cause) => cause,
+        }
+    }
+}
+
+impl Display for ApplicationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let step = match self {
+            ApplicationError::Bootstrap(_) => "bootstrap the application",
+            ApplicationError::Environment(_) => "prepare the environment",
+            ApplicationError::Context(_) => "prepare the application context",
+            ApplicationError::Refresh(_) => "refresh the application context",
+            ApplicationError::WebServer(_) => "start the web server",
+            ApplicationError::Started(_) => "notify the started listeners",
+            ApplicationError::Runner(_) => "run the application runners",
+        };
+        write!(f, "Failed to {}, {}", step, self.cause())
+    }
+}
+
+impl Error for ApplicationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.cause().as_ref())
+    }
+}
+
+/// Wrap any error as the cause of an [`ApplicationError`].
+pub(crate) fn wrap<E>(error: E) -> Arc<dyn Error + Send + Sync>
+where
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    Arc::from(error.into())
+}
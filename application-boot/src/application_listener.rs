@@ -6,24 +6,43 @@ use crate::application::{Application, RustApplication};
 use crate::bootstrap::bootstrap_registry::BootstrapRegistry;
 use crate::bootstrap::default_bootstrap_context::DefaultBootstrapContext;
 use crate::cloud::client::registry::{
-    ConsulServiceRegistry, Registration, ServiceCheck, ServiceInstance, ServiceRegistry,
+    Registration, ServiceCheck, ServiceInstance, ServiceRegistry,
 };
-use crate::env::configuration::{Configuration, ConfigurationResolver};
+use crate::env::configuration::{
+    Configuration, ConfigurationResolver, CLOUD_PROPERTY_SOURCE_NAME, CONFIG_PROPERTY_SOURCE_NAME,
+    DEFAULT_PROPERTY_SOURCE_NAME, PROFILE_CONFIG_PROPERTY_SOURCE_NAME,
+    SYSTEM_ENVIRONMENT_PROPERTY_SOURCE_NAME,
+};
+use crate::env::properties::{BootstrapProperties, DiscoveryProperties};
+use crate::env::refresh::ContextRefresher;
 use crate::logging::listener::ApplicationStartingEvent;
 use application_beans::factory::bean_factory::BeanFactory;
-use application_context::context::application_event::{ApplicationEvenType, ApplicationEvent};
+use application_context::context::application_event::{
+    ApplicationEvenType, ApplicationEvent, ListenerErrorPolicy,
+};
 use application_core::env::environment::ConfigurableEnvironment;
 use application_core::env::property::PropertySource;
 use async_trait::async_trait;
-use tracing::info;
+use tracing::{error, info, warn};
 use util::ip::LocalIp;
 
+/// A listener of the application events. Listeners borrow the application so they run one
+/// after the other, implement `ApplicationEventListener` to be called asynchronously.
 #[async_trait]
 pub trait ApplicationListener: Send + Sync {
     fn type_name(&self) -> &'static str {
         std::any::type_name::<Self>()
     }
 
+    /// Listeners with a lower order are called first.
+    fn order(&self) -> i32 {
+        0
+    }
+
+    fn error_policy(&self) -> ListenerErrorPolicy {
+        ListenerErrorPolicy::Log
+    }
+
     fn is_support(&self, event: &dyn ApplicationEvent) -> bool;
 
     async fn on_application_event(
@@ -75,17 +94,42 @@ impl ApplicationListener for BootstrapConfigFileApplicationListener {
         let application_context = application.get_application_context().await;
         let mut environment = application_context.get_environment_mut().await;
 
+        // between defaultProperties and commandLineArgs, by increasing precedence
         let native_config = Configuration::read_native_config_from_environment(&environment)?;
-        environment.add_property_source(PropertySource {
-            name: "configProperties".to_string(),
-            source: native_config,
-        });
+        environment.get_property_sources_mut().add_before(
+            DEFAULT_PROPERTY_SOURCE_NAME,
+            PropertySource {
+                name: CONFIG_PROPERTY_SOURCE_NAME.to_string(),
+                source: native_config,
+            },
+        )?;
+
+        let profile_config = Configuration::read_profile_config_from_environment(&environment)?;
+        environment.get_property_sources_mut().add_before(
+            CONFIG_PROPERTY_SOURCE_NAME,
+            PropertySource {
+                name: PROFILE_CONFIG_PROPERTY_SOURCE_NAME.to_string(),
+                source: profile_config,
+            },
+        )?;
 
-        let cloud_config = Configuration::read_remote_config_from_environment(&environment)?;
-        environment.add_property_source(PropertySource {
-            name: "cloudProperties".to_string(),
-            source: cloud_config,
-        });
+        let system_environment = Configuration::read_system_environment()?;
+        environment.get_property_sources_mut().add_before(
+            PROFILE_CONFIG_PROPERTY_SOURCE_NAME,
+            PropertySource {
+                name: SYSTEM_ENVIRONMENT_PROPERTY_SOURCE_NAME.to_string(),
+                source: system_environment,
+            },
+        )?;
+
+        let cloud_config = Configuration::read_remote_config_from_environment(&environment).await?;
+        environment.get_property_sources_mut().add_after(
+            SYSTEM_ENVIRONMENT_PROPERTY_SOURCE_NAME,
+            PropertySource {
+                name: CLOUD_PROPERTY_SOURCE_NAME.to_string(),
+                source: cloud_config,
+            },
+        )?;
 
         Ok(())
     }
@@ -96,6 +140,11 @@ pub struct DiscoveryRegistryApplicationListener {}
 
 #[async_trait]
 impl ApplicationListener for DiscoveryRegistryApplicationListener {
+    /// Only returns the registration error if `application.cloud.discovery.fail_fast` is set.
+    fn error_policy(&self) -> ListenerErrorPolicy {
+        ListenerErrorPolicy::Abort
+    }
+
     fn is_support(&self, event: &dyn ApplicationEvent) -> bool {
         event.get_event_type() == ApplicationEvenType::Started
     }
@@ -106,24 +155,24 @@ impl ApplicationListener for DiscoveryRegistryApplicationListener {
         _event: &dyn ApplicationEvent,
     ) -> Result<(), Box<dyn Error>> {
         let application_context = application.get_application_context().await;
-        let bootstrap_context = application_context
-            .get_bean_factory()
-            .get::<DefaultBootstrapContext>();
+        let bean_factory = application_context.get_bean_factory();
+        let bootstrap_context = bean_factory.get::<DefaultBootstrapContext>();
         let properties = &bootstrap_context.get_bootstrap_properties();
         if let Some(cloud) = &properties.application.cloud {
             if let Some(discovery) = &cloud.discovery {
-                let registry = bootstrap_context.get::<ConsulServiceRegistry>().unwrap();
                 let service_id = &properties.application.name;
-                let local_ip = LocalIp::get_local_addr_ip().unwrap();
-                let mut host = match hostname::get() {
-                    Ok(hostname) => hostname.to_string_lossy().to_string(),
-                    Err(_) => local_ip.to_string(),
+                let Some(registry) = bean_factory.try_get::<dyn ServiceRegistry>() else {
+                    warn!("No service registry, {} is not registered", service_id);
+                    return Ok(());
+                };
+                let (host, port) = match get_registration_address(properties, discovery) {
+                    Ok(address) => address,
+                    Err(e) if discovery.fail_fast => return Err(e),
+                    Err(e) => {
+                        error!("Register {} failed, {}", service_id, e);
+                        return Ok(());
+                    }
                 };
-                let mut port = properties.application.port.unwrap();
-                if let Some(host_properties) = &discovery.host {
-                    host = host_properties.ip.clone();
-                    port = host_properties.port;
-                }
                 let schema = if port == 443 { "https" } else { "http" };
                 let mut health_check_url =
                     format!("{}://{}:{}/actuator/health", schema, host, port);
@@ -151,7 +200,13 @@ impl ApplicationListener for DiscoveryRegistryApplicationListener {
                     service_instance,
                     service_check,
                 };
-                registry.register(&registration).await?;
+                if let Err(e) = registry.register(&registration).await {
+                    if discovery.fail_fast {
+                        return Err(e);
+                    }
+                    error!("Register {} failed, {}", service_id, e);
+                    return Ok(());
+                }
                 info!("Register {:?}", registration);
                 bootstrap_context.register(registration);
             }
@@ -160,6 +215,57 @@ impl ApplicationListener for DiscoveryRegistryApplicationListener {
     }
 }
 
+/// The host and port of `application.cloud.discovery.host`, the host name or local address
+/// and `application.port` otherwise.
+fn get_registration_address(
+    properties: &BootstrapProperties,
+    discovery: &DiscoveryProperties,
+) -> Result<(String, u16), Box<dyn Error>> {
+    if let Some(host_properties) = &discovery.host {
+        return Ok((host_properties.ip.clone(), host_properties.port));
+    }
+    let host = match hostname::get() {
+        Ok(hostname) => hostname.to_string_lossy().to_string(),
+        Err(_) => LocalIp::get_local_addr_ip()
+            .map_err(|e| format!("The local address can not be found, {}", e))?
+            .to_string(),
+    };
+    let port = properties
+        .application
+        .port
+        .ok_or("application.port or application.cloud.discovery.host must be set")?;
+    Ok((host, port))
+}
+
+/// Starts watching the config files and the Consul keys once the application started, if
+/// `application.config.refresh.enabled` is set.
+pub struct RefreshApplicationListener {}
+
+#[async_trait]
+impl ApplicationListener for RefreshApplicationListener {
+    fn is_support(&self, event: &dyn ApplicationEvent) -> bool {
+        event.get_event_type() == ApplicationEvenType::Started
+    }
+
+    async fn on_application_event(
+        &self,
+        application: &RustApplication,
+        _event: &dyn ApplicationEvent,
+    ) -> Result<(), Box<dyn Error>> {
+        let application_context = application.get_application_context().await;
+        let refresher = application_context
+            .get_bean_factory()
+            .get_bean::<ContextRefresher>();
+        if let Some(refresher) = refresher {
+            refresher
+                .start_watching()
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+}
+
 /// 应用停止时，注销服务实例
 pub struct DiscoveryDeRegistryApplicationListener {}
 
@@ -175,12 +281,11 @@ impl ApplicationListener for DiscoveryDeRegistryApplicationListener {
         _event: &dyn ApplicationEvent,
     ) -> Result<(), Box<dyn Error>> {
         let application_context = application.get_application_context().await;
-        let bootstrap_context = application_context
-            .get_bean_factory()
-            .get::<DefaultBootstrapContext>();
+        let bean_factory = application_context.get_bean_factory();
+        let bootstrap_context = bean_factory.get::<DefaultBootstrapContext>();
         let registration = bootstrap_context.get::<Registration>();
-        if let Some(service_instance) = registration {
-            let registry = bootstrap_context.get::<ConsulServiceRegistry>().unwrap();
+        let registry = bean_factory.try_get::<dyn ServiceRegistry>();
+        if let (Some(service_instance), Some(registry)) = (registration, registry) {
             let _ = registry.deregister(service_instance).await?;
             info!("DeRegister {:?}", service_instance);
         }
@@ -1,4 +1,5 @@
 use crate::application::RustApplication;
+use crate::application_error::ApplicationError;
 use crate::application_run_listeners::EventPublishingRunListener;
 use crate::bootstrap::default_bootstrap_context::DefaultBootstrapContext;
 use crate::logging::listener::ApplicationStartingEvent;
@@ -8,6 +9,8 @@ use application_context::context::application_event::{
     ApplicationStoppedEvent,
 };
 use async_trait::async_trait;
+use std::error::Error;
+use std::sync::Arc;
 
 #[async_trait]
 pub trait ApplicationRunListener: Send + Sync {
@@ -15,37 +18,38 @@ pub trait ApplicationRunListener: Send + Sync {
         &self,
         application: &RustApplication,
         bootstrap_context: &DefaultBootstrapContext,
-    );
+    ) -> Result<(), Box<dyn Error + Send + Sync>>;
     async fn environment_prepared(
         &self,
         application: &RustApplication,
         bootstrap_context: &DefaultBootstrapContext,
-    );
+    ) -> Result<(), Box<dyn Error + Send + Sync>>;
     async fn context_prepared(
         &self,
         application: &RustApplication,
         bootstrap_context: &DefaultBootstrapContext,
-    );
+    ) -> Result<(), Box<dyn Error + Send + Sync>>;
     async fn context_loaded(
         &self,
         application: &RustApplication,
         bootstrap_context: &DefaultBootstrapContext,
-    );
+    ) -> Result<(), Box<dyn Error + Send + Sync>>;
     async fn started(
         &self,
         application: &RustApplication,
         bootstrap_context: &DefaultBootstrapContext,
-    );
+    ) -> Result<(), Box<dyn Error + Send + Sync>>;
     async fn failed(
         &self,
         application: &RustApplication,
         bootstrap_context: &DefaultBootstrapContext,
-    );
+        error: &ApplicationError,
+    ) -> Result<(), Box<dyn Error + Send + Sync>>;
     async fn stopped(
         &self,
         application: &RustApplication,
         bootstrap_context: &DefaultBootstrapContext,
-    );
+    ) -> Result<(), Box<dyn Error + Send + Sync>>;
 }
 
 #[async_trait]
@@ -54,7 +58,7 @@ impl ApplicationRunListener for EventPublishingRunListener {
         &self,
         application: &RustApplication,
         bootstrap_context: &DefaultBootstrapContext,
-    ) {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let properties = bootstrap_context.get_bootstrap_properties().clone();
         self.initial_multicast
             .multicast_event(
@@ -63,66 +67,79 @@ impl ApplicationRunListener for EventPublishingRunListener {
                     bootstrap_properties: properties,
                 },
             )
-            .await;
+            .await?;
+        Ok(())
     }
 
     async fn environment_prepared(
         &self,
         application: &RustApplication,
         _bootstrap_context: &DefaultBootstrapContext,
-    ) {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         self.initial_multicast
             .multicast_event(application, ApplicationEnvironmentPreparedEvent {})
-            .await;
+            .await?;
+        Ok(())
     }
 
     async fn context_prepared(
         &self,
         application: &RustApplication,
         _bootstrap_context: &DefaultBootstrapContext,
-    ) {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         self.initial_multicast
             .multicast_event(application, ApplicationContextInitializedEvent {})
-            .await;
+            .await?;
+        Ok(())
     }
 
     async fn context_loaded(
         &self,
         application: &RustApplication,
         _bootstrap_context: &DefaultBootstrapContext,
-    ) {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         self.initial_multicast
             .multicast_event(application, ApplicationPreparedEvent {})
-            .await;
+            .await?;
+        Ok(())
     }
 
     async fn started(
         &self,
         application: &RustApplication,
         _bootstrap_context: &DefaultBootstrapContext,
-    ) {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         self.initial_multicast
             .multicast_event(application, ApplicationStartedEvent {})
-            .await;
+            .await?;
+        Ok(())
     }
 
     async fn failed(
         &self,
         application: &RustApplication,
         _bootstrap_context: &DefaultBootstrapContext,
-    ) {
+        error: &ApplicationError,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         self.initial_multicast
-            .multicast_event(application, ApplicationFailedEvent {})
-            .await;
+            .multicast_event(
+                application,
+                ApplicationFailedEvent {
+                    error: Arc::new(error.clone()),
+                },
+            )
+            .await?;
+        Ok(())
     }
 
     async fn stopped(
         &self,
         application: &RustApplication,
         _bootstrap_context: &DefaultBootstrapContext,
-    ) {
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         self.initial_multicast
             .multicast_event(application, ApplicationStoppedEvent {})
-            .await;
+            .await?;
+        Ok(())
     }
 }
@@ -1,5 +1,22 @@
 use crate::bootstrap::default_bootstrap_context::DefaultBootstrapContext;
+use application_beans::factory::condition::Condition;
+use std::any::type_name;
+use std::error::Error;
 
 pub trait BootstrapRegistryInitializer: Send + Sync {
-    fn initial(&self, context: &DefaultBootstrapContext);
+    /// Register the bootstrap instances, an error fails the application startup.
+    fn initial(
+        &self,
+        context: &DefaultBootstrapContext,
+    ) -> Result<(), Box<dyn Error + Send + Sync>>;
+
+    fn get_name(&self) -> &'static str {
+        type_name::<Self>()
+    }
+
+    /// Evaluated against the bootstrap properties, the initializer is only applied if all
+    /// conditions match.
+    fn conditions(&self) -> Vec<Box<dyn Condition>> {
+        vec![]
+    }
 }
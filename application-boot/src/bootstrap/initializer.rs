@@ -1,14 +1,23 @@
 use crate::bootstrap::bootstrap_registry::BootstrapRegistry;
 use crate::bootstrap::bootstrap_registry_initializer::BootstrapRegistryInitializer;
 use crate::bootstrap::default_bootstrap_context::DefaultBootstrapContext;
-use crate::cloud::client::registry::ConsulServiceRegistry;
+use crate::cloud::client::registry::{ConsulServiceRegistry, DiscoveryError, ServiceRegistry};
+use crate::env::refresh::ContextRefresher;
+use application_beans::factory::condition::{on_property, Condition};
 use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+use std::error::Error;
+use std::sync::Arc;
 
+/// Registers the [`ContextRefresher`] of the configuration refresh.
 pub struct RefreshBootstrapRegistryInitializer {}
 
 impl BootstrapRegistryInitializer for RefreshBootstrapRegistryInitializer {
-    fn initial(&self, _context: &DefaultBootstrapContext) {
-        todo!()
+    fn initial(
+        &self,
+        context: &DefaultBootstrapContext,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        context.register_if_absent(Arc::new(ContextRefresher::default()));
+        Ok(())
     }
 }
 
@@ -16,24 +25,34 @@ impl BootstrapRegistryInitializer for RefreshBootstrapRegistryInitializer {
 pub struct ConsulBootstrapRegistryInitializer {}
 
 impl BootstrapRegistryInitializer for ConsulBootstrapRegistryInitializer {
-    fn initial(&self, context: &DefaultBootstrapContext) {
+    fn initial(
+        &self,
+        context: &DefaultBootstrapContext,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let bootstrap_properties = context.get_bootstrap_properties();
         if let Some(cloud) = &bootstrap_properties.application.cloud {
             if let Some(discovery) = &cloud.discovery {
                 let server_properties = &discovery.server;
                 let token = server_properties.token.clone();
                 let address = &discovery.server.address;
-                let client = ConsulClient::new(
-                    ConsulClientSettingsBuilder::default()
-                        .address(address)
-                        .token(token.unwrap_or_default())
-                        .build()
-                        .unwrap(),
-                )
-                .unwrap();
-                let registry = ConsulServiceRegistry { client };
+                let discovery_error = |e: &dyn Error| DiscoveryError::new(address, e);
+                let settings = ConsulClientSettingsBuilder::default()
+                    .address(address)
+                    .token(token.unwrap_or_default())
+                    .build()
+                    .map_err(|e| discovery_error(&e))?;
+                let client = ConsulClient::new(settings).map_err(|e| discovery_error(&e))?;
+                let registry: Arc<dyn ServiceRegistry> = Arc::new(ConsulServiceRegistry { client });
                 context.register(registry);
             }
         }
+        Ok(())
+    }
+
+    fn conditions(&self) -> Vec<Box<dyn Condition>> {
+        vec![Box::new(on_property(
+            "application.cloud.discovery.server.address",
+            "",
+        ))]
     }
 }
@@ -6,6 +6,7 @@ use consulrs::service;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::error::Error;
+use std::fmt::{Display, Formatter};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct ServiceInstance {
@@ -30,8 +31,43 @@ pub struct Registration {
     pub service_check: ServiceCheck,
 }
 
+/// The discovery server could not be reached or rejected a request.
+#[derive(Debug)]
+pub struct DiscoveryError {
+    pub address: String,
+    pub message: String,
+}
+
+impl DiscoveryError {
+    /// The message of `error` followed by the messages of its sources.
+    pub fn new(address: &str, error: &dyn Error) -> Self {
+        let mut messages = vec![error.to_string()];
+        let mut source = error.source();
+        while let Some(cause) = source {
+            messages.push(cause.to_string());
+            source = cause.source();
+        }
+        DiscoveryError {
+            address: address.to_string(),
+            message: messages.join(": "),
+        }
+    }
+}
+
+impl Display for DiscoveryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Discovery server {} request failed, {}",
+            self.address, self.message
+        )
+    }
+}
+
+impl Error for DiscoveryError {}
+
 #[async_trait]
-pub trait ServiceRegistry {
+pub trait ServiceRegistry: Send + Sync {
     async fn register(&self, registration: &Registration) -> Result<(), Box<dyn Error>>;
     async fn deregister(&self, registration: &Registration) -> Result<(), Box<dyn Error>>;
 }
@@ -77,7 +113,7 @@ impl ServiceRegistry for ConsulServiceRegistry {
         .await;
         match response {
             Ok(_) => Ok(()),
-            Err(e) => Err(e.into()),
+            Err(e) => Err(DiscoveryError::new(&self.client.settings.address, &e).into()),
         }
     }
 
@@ -86,7 +122,7 @@ impl ServiceRegistry for ConsulServiceRegistry {
         let result = service::deregister(&self.client, &service_instance.instance_id, None).await;
         match result {
             Ok(_) => Ok(()),
-            Err(e) => Err(e.into()),
+            Err(e) => Err(DiscoveryError::new(&self.client.settings.address, &e).into()),
         }
     }
 }
@@ -1,32 +1,43 @@
-use crate::application::RustApplication;
-use application_context::context::application_event::ApplicationEvent;
-use tracing::info;
+use crate::application::{Application, RustApplication};
+use application_context::context::application_event::{ApplicationEvent, ListenerErrorPolicy};
+use application_context::context::application_event_multi_caster::{
+    ApplicationEventError, ListenerErrorCollector,
+};
+use std::sync::Arc;
 
 pub struct ApplicationEventMultiCaster {}
 
 impl ApplicationEventMultiCaster {
-    pub async fn multicast_event<T: ApplicationEvent>(
+    /// Deliver `event` to the application listeners by order, then publish it to the application context.
+    pub async fn multicast_event<T: ApplicationEvent + 'static>(
         &self,
         application: &RustApplication,
         event: T,
-    ) {
-        let listeners = application.listeners.read().await;
-        let listeners = listeners.iter();
-        for listener in listeners {
-            if listener.is_support(&event) {
-                let result = listener.on_application_event(application, &event).await;
-                match result {
-                    Ok(_) => {}
-                    Err(e) => {
-                        info!(
-                            "{} listen on {:?} Event failed, {:?}",
-                            listener.type_name(),
-                            event.get_event_type(),
-                            e
-                        )
-                    }
+    ) -> Result<(), ApplicationEventError> {
+        let event = Arc::new(event);
+        let mut errors = ListenerErrorCollector::new(event.as_ref());
+        {
+            let mut listeners: Vec<_> = application.get_listeners().iter().collect();
+            listeners.sort_by_key(|listener| listener.order());
+            for listener in listeners {
+                if listener.is_support(event.as_ref()) {
+                    let result = listener
+                        .on_application_event(application, event.as_ref())
+                        .await;
+                    errors.handle(
+                        listener.type_name(),
+                        listener.error_policy(),
+                        result.map_err(|e| e.to_string()),
+                    )?;
                 }
             }
         }
+        let application_context = application.get_application_context().await;
+        if let Err(e) = application_context.publish_event(event).await {
+            for (listener, message) in e.failures {
+                errors.handle(&listener, ListenerErrorPolicy::Collect, Err(message))?;
+            }
+        }
+        errors.into_result()
     }
 }
@@ -0,0 +1,302 @@
+use crate::application_error::ApplicationError;
+use crate::application_listener::DiscoveryRegistryApplicationListener;
+use crate::cloud::client::registry::DiscoveryError;
+use crate::diagnostics::failure_analyzer::{find_cause, FailureAnalysis, FailureAnalyzer};
+use crate::env::properties::{
+    BootstrapFileNotFoundError, BOOTSTRAP_LOCATION_ENV, BOOTSTRAP_LOCATION_OPTION,
+};
+use application_context::context::application_event_multi_caster::ApplicationEventError;
+use application_core::env::configuration_properties::BindError;
+use application_web::server::PortInUseError;
+use config::ConfigError;
+use std::any::type_name;
+
+/// The bootstrap file set explicitly does not exist.
+pub struct BootstrapFileNotFoundFailureAnalyzer;
+
+impl FailureAnalyzer for BootstrapFileNotFoundFailureAnalyzer {
+    fn analyze(&self, error: &ApplicationError) -> Option<FailureAnalysis> {
+        let cause = find_cause::<BootstrapFileNotFoundError>(error)?;
+        Some(FailureAnalysis {
+            description: format!(
+                "No bootstrap file was found at {}, a location without extension or a directory \
+                 is looked up as a toml, yaml, yml or json file.",
+                cause.location
+            ),
+            action: format!(
+                "Create the bootstrap file or correct the location set by the --{} option, the {} \
+                 environment variable or the application builder.",
+                BOOTSTRAP_LOCATION_OPTION, BOOTSTRAP_LOCATION_ENV
+            ),
+        })
+    }
+}
+
+/// The web server port is already in use.
+pub struct PortInUseFailureAnalyzer;
+
+impl FailureAnalyzer for PortInUseFailureAnalyzer {
+    fn analyze(&self, error: &ApplicationError) -> Option<FailureAnalysis> {
+        let cause = find_cause::<PortInUseError>(error)?;
+        Some(FailureAnalysis {
+            description: format!(
+                "Web server failed to start. Port {} was already in use.",
+                cause.port
+            ),
+            action: format!(
+                "Identify and stop the process that's listening on port {} or configure this \
+                 application to listen on another port with the application.port property.",
+                cause.port
+            ),
+        })
+    }
+}
+
+/// A configuration file that can not be parsed or properties that can not be deserialized.
+pub struct ConfigErrorFailureAnalyzer;
+
+impl FailureAnalyzer for ConfigErrorFailureAnalyzer {
+    fn analyze(&self, error: &ApplicationError) -> Option<FailureAnalysis> {
+        let cause = find_cause::<ConfigError>(error)?;
+        if let ConfigError::FileParse { uri, cause } = cause {
+            return Some(FailureAnalysis {
+                description: format!(
+                    "Failed to parse the configuration file {}:\n\n    {}",
+                    uri.as_deref().unwrap_or("<string>"),
+                    cause
+                ),
+                action: "Fix the syntax of the configuration file.".to_string(),
+            });
+        }
+        let key = match cause {
+            ConfigError::NotFound(key) => Some(key),
+            ConfigError::Type { key, .. } | ConfigError::At { key, .. } => key.as_ref(),
+            _ => None,
+        };
+        let property = match key {
+            Some(key) => format!("the property {}", key),
+            None => "the properties".to_string(),
+        };
+        Some(FailureAnalysis {
+            description: format!("Failed to bind {}:\n\n    {}", property, cause),
+            action: format!(
+                "Update bootstrap.toml, the configuration files or the environment variables so \
+                 that {} is set with a value of the expected type.",
+                property
+            ),
+        })
+    }
+}
+
+/// Configuration properties with missing, unconvertible or invalid properties.
+pub struct BindErrorFailureAnalyzer;
+
+impl FailureAnalyzer for BindErrorFailureAnalyzer {
+    fn analyze(&self, error: &ApplicationError) -> Option<FailureAnalysis> {
+        let cause = find_cause::<BindError>(error)?;
+        let mut description = "Binding to the configuration properties failed:\n".to_string();
+        for property in &cause.invalid_properties {
+            description.push_str(&format!("\n    Property: {}", property.key));
+            if let Some(value) = &property.value {
+                description.push_str(&format!("\n    Value: \"{}\"", value));
+            }
+            if let Some(origin) = &property.origin {
+                description.push_str(&format!("\n    Origin: {}", origin));
+            }
+            description.push_str(&format!("\n    Reason: {}\n", property.reason));
+        }
+        Some(FailureAnalysis {
+            description,
+            action: "Update the configuration files, the environment variables or the \
+                     command-line arguments so that the properties above are valid."
+                .to_string(),
+        })
+    }
+}
+
+/// The discovery server can not be reached or rejected the registration of the application.
+pub struct DiscoveryFailureAnalyzer;
+
+impl FailureAnalyzer for DiscoveryFailureAnalyzer {
+    fn analyze(&self, error: &ApplicationError) -> Option<FailureAnalysis> {
+        let description = match find_cause::<DiscoveryError>(error) {
+            Some(cause) => format!(
+                "Failed to connect to the discovery server {}:\n\n    {}",
+                cause.address, cause.message
+            ),
+            // the registration runs in a listener, whose errors only keep their message
+            None => {
+                let cause = find_cause::<ApplicationEventError>(error)?;
+                let (_, message) = cause.failures.iter().find(|(listener, _)| {
+                    listener == type_name::<DiscoveryRegistryApplicationListener>()
+                })?;
+                format!(
+                    "Failed to register the application at the discovery server:\n\n    {}",
+                    message
+                )
+            }
+        };
+        Some(FailureAnalysis {
+            description,
+            action:
+                "Check that the discovery server of application.cloud.discovery.server.address \
+                     is running and reachable, and that application.cloud.discovery.server.token \
+                     is valid."
+                    .to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application_error::wrap;
+    use application_context::context::application_event::ApplicationEvenType;
+    use application_core::env::configuration_properties::InvalidProperty;
+    use application_core::env::origin::PropertyOrigin;
+
+    #[test]
+    fn a_missing_bootstrap_file_is_analyzed() {
+        let error = ApplicationError::Bootstrap(wrap(BootstrapFileNotFoundError {
+            location: "/etc/app".to_string(),
+        }));
+
+        let analysis = BootstrapFileNotFoundFailureAnalyzer
+            .analyze(&error)
+            .unwrap();
+
+        assert_eq!(
+            analysis.description,
+            "No bootstrap file was found at /etc/app, a location without extension or a \
+             directory is looked up as a toml, yaml, yml or json file."
+        );
+        assert_eq!(
+            analysis.action,
+            "Create the bootstrap file or correct the location set by the --bootstrap option, \
+             the BOOTSTRAP_LOCATION environment variable or the application builder."
+        );
+    }
+
+    #[test]
+    fn a_port_in_use_is_analyzed() {
+        let error = ApplicationError::WebServer(wrap(PortInUseError { port: 8080 }));
+
+        let analysis = PortInUseFailureAnalyzer.analyze(&error).unwrap();
+
+        assert_eq!(
+            analysis.description,
+            "Web server failed to start. Port 8080 was already in use."
+        );
+        assert!(analysis.action.contains("listening on port 8080"));
+    }
+
+    #[test]
+    fn config_errors_are_analyzed() {
+        let error = ApplicationError::Environment(wrap(ConfigError::FileParse {
+            uri: Some("config.toml".to_string()),
+            cause: "expected a value".into(),
+        }));
+
+        let analysis = ConfigErrorFailureAnalyzer.analyze(&error).unwrap();
+
+        assert_eq!(
+            analysis.description,
+            "Failed to parse the configuration file config.toml:\n\n    expected a value"
+        );
+        assert_eq!(analysis.action, "Fix the syntax of the configuration file.");
+
+        let error = ApplicationError::Environment(wrap(ConfigError::NotFound(
+            "application.port".to_string(),
+        )));
+
+        let analysis = ConfigErrorFailureAnalyzer.analyze(&error).unwrap();
+
+        assert!(analysis
+            .description
+            .starts_with("Failed to bind the property application.port:"));
+        assert!(analysis.action.ends_with(
+            "so that the property application.port is set with a value of the expected type."
+        ));
+    }
+
+    #[test]
+    fn bind_errors_are_analyzed() {
+        let error = ApplicationError::Context(wrap(BindError {
+            invalid_properties: vec![InvalidProperty {
+                key: "app.mail.port".to_string(),
+                value: Some("0".to_string()),
+                origin: Some(PropertyOrigin {
+                    source: "configProperties".to_string(),
+                    location: Some("config.toml".to_string()),
+                    line: Some(3),
+                }),
+                reason: "must be between 1 and 65535".to_string(),
+            }],
+        }));
+
+        let analysis = BindErrorFailureAnalyzer.analyze(&error).unwrap();
+
+        assert_eq!(
+            analysis.description,
+            "Binding to the configuration properties failed:\n\
+             \n    Property: app.mail.port\
+             \n    Value: \"0\"\
+             \n    Origin: configProperties (config.toml:3)\
+             \n    Reason: must be between 1 and 65535\n"
+        );
+        assert!(analysis
+            .action
+            .ends_with("so that the properties above are valid."));
+    }
+
+    #[test]
+    fn discovery_failures_are_analyzed() {
+        let error = ApplicationError::Bootstrap(wrap(DiscoveryError {
+            address: "http://localhost:8500".to_string(),
+            message: "connection refused".to_string(),
+        }));
+
+        let analysis = DiscoveryFailureAnalyzer.analyze(&error).unwrap();
+
+        assert_eq!(
+            analysis.description,
+            "Failed to connect to the discovery server http://localhost:8500:\n\n    \
+             connection refused"
+        );
+        assert!(analysis
+            .action
+            .starts_with("Check that the discovery server"));
+
+        let error = ApplicationError::Started(wrap(ApplicationEventError {
+            event_type: ApplicationEvenType::Started,
+            failures: vec![(
+                type_name::<DiscoveryRegistryApplicationListener>().to_string(),
+                "connection refused".to_string(),
+            )],
+        }));
+
+        let analysis = DiscoveryFailureAnalyzer.analyze(&error).unwrap();
+
+        assert_eq!(
+            analysis.description,
+            "Failed to register the application at the discovery server:\n\n    \
+             connection refused"
+        );
+    }
+
+    #[test]
+    fn unknown_errors_are_not_analyzed() {
+        let error = ApplicationError::Runner(wrap("the runner failed"));
+        let analyzers: Vec<Box<dyn FailureAnalyzer>> = vec![
+            Box::new(BootstrapFileNotFoundFailureAnalyzer),
+            Box::new(PortInUseFailureAnalyzer),
+            Box::new(ConfigErrorFailureAnalyzer),
+            Box::new(BindErrorFailureAnalyzer),
+            Box::new(DiscoveryFailureAnalyzer),
+        ];
+
+        for analyzer in analyzers {
+            assert!(analyzer.analyze(&error).is_none());
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use crate::application_error::ApplicationError;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Explains to the user why the application failed to start and what to do about it.
+#[derive(Debug, Clone)]
+pub struct FailureAnalysis {
+    pub description: String,
+    pub action: String,
+}
+
+impl Display for FailureAnalysis {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "\n\n***************************\nAPPLICATION FAILED TO START\n***************************\n\nDescription:\n\n{}\n\nAction:\n\n{}\n",
+            self.description, self.action
+        )
+    }
+}
+
+/// Turns the startup errors it knows into a [`FailureAnalysis`].
+pub trait FailureAnalyzer: Send + Sync {
+    /// `None` if the analyzer does not recognize the error.
+    fn analyze(&self, error: &ApplicationError) -> Option<FailureAnalysis>;
+}
+
+/// The first error of type `E` in the source chain of `error`.
+pub fn find_cause<E: Error + 'static>(error: &ApplicationError) -> Option<&E> {
+    let mut source: Option<&(dyn Error + 'static)> = Some(error);
+    while let Some(cause) = source {
+        if let Some(cause) = cause.downcast_ref::<E>() {
+            return Some(cause);
+        }
+        source = cause.source();
+    }
+    None
+}
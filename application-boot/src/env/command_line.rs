@@ -0,0 +1,26 @@
+use crate::runner::ApplicationArguments;
+use application_core::env::property::PropertySource;
+use config::{Config, ConfigError};
+
+/// The name of the property source of the command-line options.
+pub const COMMAND_LINE_PROPERTY_SOURCE_NAME: &str = "commandLineArgs";
+
+/// The options as properties, `--name` sets `true` and a repeated option a list.
+///
+/// The non-option arguments are left to the runners, see [`ApplicationArguments`].
+pub fn command_line_property_source(
+    args: &ApplicationArguments,
+) -> Result<PropertySource, ConfigError> {
+    let mut builder = Config::builder();
+    for name in args.get_option_names() {
+        builder = match args.get_option_values(name).unwrap_or_default() {
+            [] => builder.set_override(name, true)?,
+            [value] => builder.set_override(name, value.as_str())?,
+            values => builder.set_override(name, values.to_vec())?,
+        };
+    }
+    Ok(PropertySource {
+        name: COMMAND_LINE_PROPERTY_SOURCE_NAME.to_string(),
+        source: builder.build()?,
+    })
+}
@@ -2,47 +2,85 @@ use std::error::Error;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::env::origin::{OriginTrackedFile, SystemEnvironment};
 use crate::env::properties::{CloudConfigProperties, CloudProperties};
 use application_core::env::environment::{ApplicationEnvironment, Environment};
 use application_core::env::property_resolver::PropertyResolver;
-use async_std::task::block_on;
 use async_trait::async_trait;
-use config::Case::Snake;
 use config::Config;
 use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+use consulrs::error::ClientError;
 use consulrs::kv;
 use tempfile::{tempdir, TempDir};
 use tracing::info;
 
+/// The names of the built-in property sources, by precedence; `commandLineArgs` comes first,
+/// see [`crate::env::command_line`].
+pub const SYSTEM_ENVIRONMENT_PROPERTY_SOURCE_NAME: &str = "systemEnvironment";
+pub const CLOUD_PROPERTY_SOURCE_NAME: &str = "cloudProperties";
+pub const PROFILE_CONFIG_PROPERTY_SOURCE_NAME: &str = "profileConfigProperties";
+pub const CONFIG_PROPERTY_SOURCE_NAME: &str = "configProperties";
+pub const DEFAULT_PROPERTY_SOURCE_NAME: &str = "defaultProperties";
+
 pub struct Configuration {}
 
 #[async_trait]
 pub trait ConfigurationResolver {
+    /// The base config files, e.g. `config.toml`, without the profile ones.
     fn read_native_config_from_environment(
         env: &ApplicationEnvironment,
     ) -> Result<Config, Box<dyn Error>> {
-        let activate_profiles = &env.get_active_profiles();
-        let config_locations = &env.get_config_locations();
-        let config_file_names = &env.get_file_names();
+        let mut builder = Config::builder();
+        for config_file in Self::get_base_config_files(env) {
+            builder = builder.add_source(OriginTrackedFile::new(config_file));
+        }
+        Ok(builder.build()?)
+    }
 
+    /// The config files of the active profiles other than `default`, e.g. `config-dev.toml`,
+    /// the last profile wins.
+    fn read_profile_config_from_environment(
+        env: &ApplicationEnvironment,
+    ) -> Result<Config, Box<dyn Error>> {
         let mut builder = Config::builder();
-        let config_files =
-            Self::get_native_config_files(activate_profiles, config_locations, config_file_names);
-        for config_file in config_files {
-            builder = builder.add_source(config::File::with_name(&config_file));
+        for config_file in Self::get_profile_config_files(env) {
+            builder = builder.add_source(OriginTrackedFile::new(config_file));
         }
-        builder = builder.add_source(
-            config::Environment::default()
-                .separator("_")
-                .convert_case(Snake)
-                .try_parsing(true),
-        );
+        Ok(builder.build()?)
+    }
 
-        let config = builder.build().unwrap();
+    /// The existing base config files, `./config.toml` if none is configured.
+    fn get_base_config_files(env: &ApplicationEnvironment) -> Vec<String> {
+        let mut config_files =
+            Self::get_native_config_files(None, &env.get_config_locations(), &env.get_file_names());
+        if config_files.is_empty() && Path::new("./config.toml").exists() {
+            config_files.push("./config.toml".to_string());
+        }
+        config_files
+    }
+
+    /// The existing config files of the active profiles other than `default`.
+    fn get_profile_config_files(env: &ApplicationEnvironment) -> Vec<String> {
+        let config_locations = &env.get_config_locations();
+        let config_file_names = &env.get_file_names();
+        env.get_active_profiles()
+            .iter()
+            .filter(|profile| *profile != "default")
+            .flat_map(|profile| {
+                Self::get_native_config_files(Some(profile), config_locations, config_file_names)
+            })
+            .collect()
+    }
+
+    /// The environment variables, `APPLICATION_PORT` is `application.port`.
+    fn read_system_environment() -> Result<Config, Box<dyn Error>> {
+        let config = Config::builder().add_source(SystemEnvironment).build()?;
         Ok(config)
     }
 
-    fn read_remote_config_from_environment(
+    /// The config of the active profiles in Consul, if `application.cloud.config` is enabled.
+    /// A missing key is skipped, any other failure of Consul is returned.
+    async fn read_remote_config_from_environment(
         env: &ApplicationEnvironment,
     ) -> Result<Config, Box<dyn Error>> {
         let activate_profiles = &env.get_active_profiles();
@@ -53,100 +91,89 @@ pub trait ConfigurationResolver {
         if let Some(cloud) = &cloud_properties {
             if let Some(cloud_config) = &cloud.config {
                 if cloud_config.enabled {
-                    let application_name = env.get_property::<String>("application.name").unwrap();
+                    let application_name = env
+                        .get_property::<String>("application.name")
+                        .ok_or("The application name is required by the cloud config")?;
                     let config_files = Self::get_remote_config_files(
                         cloud_config,
                         activate_profiles,
                         &application_name,
                         &dir,
-                    );
-                    let config_files = block_on(config_files);
-                    if let Ok(config_files) = config_files {
-                        for config_file in config_files {
-                            builder = builder.add_source(config::File::from(config_file));
-                        }
+                    )
+                    .await?;
+                    for (config_file, location) in config_files {
+                        builder = builder
+                            .add_source(OriginTrackedFile::with_location(config_file, &location));
                     }
                 }
             }
         }
-        let config = builder.build().unwrap();
-        Ok(config)
+        Ok(builder.build()?)
     }
 
+    /// The existing config files of `profile`, the base files for `None`.
     fn get_native_config_files(
-        activate_profiles: &Vec<String>,
+        profile: Option<&str>,
         config_locations: &Option<Vec<String>>,
         config_file_names: &Option<Vec<String>>,
     ) -> Vec<String> {
         let mut config_files = Vec::new();
-        if let Some(locations) = config_locations {
+        if let (Some(locations), Some(file_names)) = (config_locations, config_file_names) {
             for location in locations {
-                if let Some(file_names) = config_file_names {
-                    for file_name in file_names {
-                        let dot_index = file_name.find('.').unwrap();
-                        for profile in activate_profiles {
-                            let mut full_name = location.to_string() + "/" + file_name;
-                            if profile != "default" {
-                                full_name = location.to_string()
-                                    + "/"
-                                    + &full_name[0..dot_index]
-                                    + "-"
-                                    + profile
-                                    + &full_name[dot_index..];
-                            }
-                            let path = Path::new(&full_name);
-                            if path.exists() {
-                                config_files.push(full_name.clone());
-                            }
+                for file_name in file_names {
+                    let file_name = match (profile, file_name.split_once('.')) {
+                        (Some(profile), Some((stem, extension))) => {
+                            format!("{}-{}.{}", stem, profile, extension)
                         }
+                        (Some(profile), None) => format!("{}-{}", file_name, profile),
+                        (None, _) => file_name.clone(),
+                    };
+                    let full_name = location.to_string() + "/" + &file_name;
+                    if Path::new(&full_name).exists() {
+                        config_files.push(full_name);
                     }
                 }
             }
         }
-        if config_files.is_empty() {
-            let path = Path::new("./config.toml");
-            if path.exists() {
-                config_files.push("./config.toml".to_string());
-            }
-        }
         config_files
     }
 
     async fn get_remote_config_files(
         cloud_config: &CloudConfigProperties,
-        activate_profiles: &Vec<String>,
+        activate_profiles: &[String],
         application_name: &str,
         dir: &TempDir,
-    ) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    ) -> Result<Vec<(PathBuf, String)>, Box<dyn Error>> {
         let client = ConsulClient::new(
             ConsulClientSettingsBuilder::default()
                 .address(&cloud_config.address)
                 .token(cloud_config.token.clone().unwrap_or_default())
-                .build()
-                .unwrap(),
-        )
-        .unwrap();
+                .build()?,
+        )?;
         let mut config_files = Vec::new();
-        for profile in activate_profiles {
-            let mut key: String = profile.to_string() + "/" + application_name;
-            if profile == "default" {
-                key = application_name.to_string();
-            }
+        for (profile, key) in get_remote_config_keys(activate_profiles, application_name) {
             let result = kv::raw(&client, &key, None).await;
             match result {
                 Ok(result) => {
-                    // write to temp file
+                    // write to temp file, one per profile
                     let vec = result.response;
-                    let file_path = dir.path().join(application_name.to_string() + ".toml");
+                    let file_path = dir
+                        .path()
+                        .join(format!("{}-{}.toml", profile, application_name));
                     fs::write(&file_path, vec)?;
-                    config_files.push(file_path);
+                    let location = format!("consul key '{}' at {}", key, cloud_config.address);
+                    config_files.push((file_path, location));
                 }
-                Err(e) => {
+                Err(ClientError::APIError { code: 404, .. }) => {
                     info!(
-                        "application {} config not found on cloud, {:?}",
-                        application_name, e
+                        "application {} config not found on cloud, key '{}'",
+                        application_name, key
                     );
                 }
+                Err(e) => {
+                    let message = format!("Read of the consul key '{}' failed, {}", key, e);
+                    return Err(message.into());
+                }
             }
         }
 
@@ -155,3 +182,93 @@ pub trait ConfigurationResolver {
 }
 
 impl ConfigurationResolver for Configuration {}
+
+/// The Consul keys of the config of each profile with their profile, `{profile}/{name}`,
+/// the key of the `default` profile is the application name.
+pub fn get_remote_config_keys(
+    activate_profiles: &[String],
+    application_name: &str,
+) -> Vec<(String, String)> {
+    activate_profiles
+        .iter()
+        .map(|profile| {
+            let key = if profile == "default" {
+                application_name.to_string()
+            } else {
+                format!("{}/{}", profile, application_name)
+            };
+            (profile.clone(), key)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use application_core::env::environment::ConfigurableEnvironment;
+    use application_core::env::property::PropertySource;
+    use config::Source;
+
+    fn environment(properties: &[(&str, &str)]) -> ApplicationEnvironment {
+        let mut builder = Config::builder();
+        for (key, value) in properties {
+            builder = builder.set_override(*key, *value).unwrap();
+        }
+        let mut environment = ApplicationEnvironment::new(vec!["default".to_string()], None, None);
+        environment.add_property_source(PropertySource {
+            name: "test".to_string(),
+            source: builder.build().unwrap(),
+        });
+        environment
+    }
+
+    #[tokio::test]
+    async fn a_disabled_cloud_config_is_empty() {
+        let environment = environment(&[
+            ("application.cloud.config.enabled", "false"),
+            ("application.cloud.config.address", "http://127.0.0.1:1"),
+        ]);
+
+        let config = Configuration::read_remote_config_from_environment(&environment)
+            .await
+            .unwrap();
+
+        assert!(config.collect().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn a_failure_of_consul_is_returned() {
+        let environment = environment(&[
+            ("application.name", "demo"),
+            ("application.cloud.config.enabled", "true"),
+            ("application.cloud.config.address", "http://127.0.0.1:1"),
+        ]);
+
+        let Err(error) = Configuration::read_remote_config_from_environment(&environment).await
+        else {
+            panic!("consul is not running");
+        };
+
+        assert!(error
+            .to_string()
+            .starts_with("Read of the consul key 'demo' failed"));
+    }
+
+    #[tokio::test]
+    async fn the_cloud_config_requires_the_application_name() {
+        let environment = environment(&[
+            ("application.cloud.config.enabled", "true"),
+            ("application.cloud.config.address", "http://127.0.0.1:1"),
+        ]);
+
+        let Err(error) = Configuration::read_remote_config_from_environment(&environment).await
+        else {
+            panic!("the application name is not set");
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "The application name is required by the cloud config"
+        );
+    }
+}
@@ -1,2 +1,5 @@
+pub mod command_line;
 pub mod configuration;
+pub mod origin;
 pub mod properties;
+pub mod refresh;
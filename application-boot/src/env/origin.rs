@@ -0,0 +1,360 @@
+use application_core::env::origin::origin_location;
+use config::Case::Snake;
+use config::{ConfigError, FileFormat, Map, Source, Value, ValueKind};
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+lazy_static::lazy_static! {
+    /// The origin of the environment variables set by the `.env` files, by variable name.
+    static ref DOTENV_ORIGINS: RwLock<HashMap<String, String>> = RwLock::new(HashMap::new());
+}
+
+/// Load the `.env` file at `path`, or the one found from the working directory, keeping the
+/// variables set already and remembering the file and line of the others.
+pub fn load_dotenv(path: Option<&Path>) {
+    let existing: HashSet<OsString> = std::env::vars_os().map(|(name, _)| name).collect();
+    let path = match path {
+        Some(path) => dotenvy::from_path(path).map(|_| path.to_path_buf()),
+        None => dotenvy::dotenv(),
+    };
+    let Ok(path) = path else {
+        return;
+    };
+    let (Ok(content), Ok(variables)) = (fs::read_to_string(&path), dotenvy::from_path_iter(&path))
+    else {
+        return;
+    };
+    let mut origins = DOTENV_ORIGINS.write().unwrap_or_else(|e| e.into_inner());
+    for (name, _) in variables.flatten() {
+        if !existing.contains(&OsString::from(&name)) {
+            let line = find_dotenv_line(&content, &name);
+            let origin = origin_location(&path.display().to_string(), line);
+            origins.insert(name, origin);
+        }
+    }
+}
+
+/// The environment variables as properties, `APP_MAIL_PORT` is `app.mail.port`, each value
+/// tracks its variable or the `.env` file that set it.
+///
+/// A table can not hold a value, the variable of a key that prefixes another one is dropped,
+/// e.g. `CARGO_PKG_VERSION` for `CARGO_PKG_VERSION_PRE`, instead of the one that happens to
+/// come last.
+#[derive(Debug, Clone, Default)]
+pub struct SystemEnvironment;
+
+impl Source for SystemEnvironment {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, ConfigError> {
+        let mut properties = config::Environment::default()
+            .separator("_")
+            .convert_case(Snake)
+            .try_parsing(true)
+            .collect()?;
+        let tables: HashSet<String> = properties
+            .keys()
+            .flat_map(|key| {
+                key.match_indices('.')
+                    .map(|(index, _)| key[..index].to_string())
+            })
+            .collect();
+        properties.retain(|key, _| !tables.contains(key));
+        let variables: HashMap<String, String> = std::env::vars_os()
+            .filter_map(|(name, _)| name.into_string().ok())
+            .map(|name| (name.to_lowercase().replace('_', "."), name))
+            .collect();
+        let dotenv_origins = DOTENV_ORIGINS.read().unwrap_or_else(|e| e.into_inner());
+        Ok(properties
+            .into_iter()
+            .map(|(key, value)| {
+                let origin = variables
+                    .get(&key)
+                    .map(|name| match dotenv_origins.get(name) {
+                        Some(origin) => origin.clone(),
+                        None => format!("environment variable {}", name),
+                    });
+                (key, Value::new(origin.as_ref(), value.kind))
+            })
+            .collect())
+    }
+}
+
+/// A configuration file whose values track their file and line, reported under `location`.
+#[derive(Debug, Clone)]
+pub struct OriginTrackedFile {
+    path: PathBuf,
+    location: String,
+}
+
+impl OriginTrackedFile {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let location = path.display().to_string();
+        OriginTrackedFile { path, location }
+    }
+
+    /// A file holding a copy of a remote configuration, e.g. a Consul key.
+    pub fn with_location(path: impl Into<PathBuf>, location: &str) -> Self {
+        OriginTrackedFile {
+            path: path.into(),
+            location: location.to_string(),
+        }
+    }
+}
+
+impl Source for OriginTrackedFile {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, ConfigError> {
+        let mut properties = config::File::from(self.path.as_path()).collect()?;
+        let content = fs::read_to_string(&self.path).unwrap_or_default();
+        let format = match self
+            .path
+            .extension()
+            .and_then(|extension| extension.to_str())
+        {
+            Some("toml") => Some(FileFormat::Toml),
+            Some("yaml" | "yml") => Some(FileFormat::Yaml),
+            Some("json") => Some(FileFormat::Json),
+            _ => None,
+        };
+        let find_line = |key: &[String]| match format {
+            Some(FileFormat::Toml) => find_toml_line(&content, key),
+            Some(_) => find_indented_line(&content, key),
+            None => None,
+        };
+        track_origins(&mut properties, &mut vec![], &self.location, &find_line);
+        Ok(properties)
+    }
+}
+
+/// Set the origin of the values of `properties` to `location` and the line of their key.
+fn track_origins(
+    properties: &mut Map<String, Value>,
+    key: &mut Vec<String>,
+    location: &str,
+    find_line: &dyn Fn(&[String]) -> Option<usize>,
+) {
+    for (name, value) in properties.iter_mut() {
+        key.push(name.clone());
+        match &mut value.kind {
+            ValueKind::Table(table) => track_origins(table, key, location, find_line),
+            kind => {
+                let origin = origin_location(location, find_line(key));
+                *value = Value::new(Some(&origin), std::mem::take(kind));
+            }
+        }
+        key.pop();
+    }
+}
+
+/// The segments of a dotted key, unquoted.
+fn split_key(key: &str) -> Vec<String> {
+    key.split('.')
+        .map(|segment| segment.trim().trim_matches(['"', '\'']).to_string())
+        .collect()
+}
+
+/// The line of `key` in a toml file, or of the inline table or array holding it.
+fn find_toml_line(content: &str, key: &[String]) -> Option<usize> {
+    let mut table = vec![];
+    let mut holder = None;
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if let Some(header) = line.strip_prefix('[') {
+            let header = header.trim_start_matches('[');
+            if let Some((header, _)) = header.split_once(']') {
+                table = split_key(header);
+            }
+            continue;
+        }
+        if line.starts_with('#') {
+            continue;
+        }
+        let Some((name, _)) = line.split_once('=') else {
+            continue;
+        };
+        let mut line_key = table.clone();
+        line_key.extend(split_key(name));
+        if line_key == key {
+            return Some(index + 1);
+        }
+        if holder.is_none() && key.starts_with(&line_key) {
+            holder = Some(index + 1);
+        }
+    }
+    holder
+}
+
+/// The line of `key` in a yaml or indented json file, the nesting follows the indentation.
+fn find_indented_line(content: &str, key: &[String]) -> Option<usize> {
+    let mut parents: Vec<(usize, String)> = vec![];
+    let mut holder = None;
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('-') {
+            continue;
+        }
+        let Some((name, _)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let indent = line.len() - trimmed.len();
+        parents.retain(|(parent_indent, _)| *parent_indent < indent);
+        parents.push((indent, name.trim().trim_matches(['"', '\'']).to_string()));
+        let line_key: Vec<&String> = parents.iter().map(|(_, name)| name).collect();
+        if line_key.len() == key.len() && line_key.iter().zip(key).all(|(a, b)| *a == b) {
+            return Some(index + 1);
+        }
+        if holder.is_none()
+            && line_key.len() < key.len()
+            && line_key.iter().zip(key).all(|(a, b)| *a == b)
+            && !trimmed.trim_end().ends_with(':')
+        {
+            holder = Some(index + 1);
+        }
+    }
+    holder
+}
+
+/// The line of `NAME=` or `export NAME=` in a `.env` file.
+fn find_dotenv_line(content: &str, name: &str) -> Option<usize> {
+    content
+        .lines()
+        .position(|line| {
+            let line = line.trim_start();
+            let line = line.strip_prefix("export ").unwrap_or(line).trim_start();
+            line.strip_prefix(name)
+                .is_some_and(|rest| rest.trim_start().starts_with('='))
+        })
+        .map(|index| index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(key: &str) -> Vec<String> {
+        split_key(key)
+    }
+
+    #[test]
+    fn toml_keys_are_found_in_their_table() {
+        let content = r#"# comment
+name = "top"
+
+[application]
+name = "demo"
+port = 8080
+
+[application.cloud.discovery]
+server.address = "http://localhost:8500"
+"host".ip = "127.0.0.1"
+
+[[servers]]
+name = "first"
+"#;
+        assert_eq!(find_toml_line(content, &key("name")), Some(2));
+        assert_eq!(find_toml_line(content, &key("application.name")), Some(5));
+        assert_eq!(find_toml_line(content, &key("application.port")), Some(6));
+        assert_eq!(
+            find_toml_line(content, &key("application.cloud.discovery.server.address")),
+            Some(9)
+        );
+        assert_eq!(
+            find_toml_line(content, &key("application.cloud.discovery.host.ip")),
+            Some(10)
+        );
+        assert_eq!(find_toml_line(content, &key("servers.name")), Some(13));
+        assert_eq!(find_toml_line(content, &key("application.missing")), None);
+    }
+
+    #[test]
+    fn toml_keys_of_inline_tables_are_found_at_their_holder() {
+        let content = r#"[application]
+logger = { level = "info", file = "app.log" }
+profiles = ["dev", "local"]
+"#;
+        assert_eq!(
+            find_toml_line(content, &key("application.logger.level")),
+            Some(2)
+        );
+        assert_eq!(
+            find_toml_line(content, &key("application.logger.file")),
+            Some(2)
+        );
+        assert_eq!(
+            find_toml_line(content, &key("application.profiles")),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn yaml_keys_follow_the_indentation() {
+        let content = r#"# comment
+application:
+  name: demo
+  cloud:
+    discovery:
+      server:
+        address: http://localhost:8500
+  port: 8080
+logger: { level: info }
+profiles:
+  - dev
+other:
+  name: other
+"#;
+        assert_eq!(
+            find_indented_line(content, &key("application.name")),
+            Some(3)
+        );
+        assert_eq!(
+            find_indented_line(content, &key("application.cloud.discovery.server.address")),
+            Some(7)
+        );
+        assert_eq!(
+            find_indented_line(content, &key("application.port")),
+            Some(8)
+        );
+        // a flow mapping holds its keys
+        assert_eq!(find_indented_line(content, &key("logger.level")), Some(9));
+        assert_eq!(find_indented_line(content, &key("profiles")), Some(10));
+        assert_eq!(find_indented_line(content, &key("other.name")), Some(13));
+        assert_eq!(
+            find_indented_line(content, &key("application.missing")),
+            None
+        );
+    }
+
+    #[test]
+    fn json_keys_follow_the_indentation() {
+        let content = r#"{
+  "application": {
+    "name": "demo",
+    "port": 8080
+  }
+}
+"#;
+        assert_eq!(
+            find_indented_line(content, &key("application.port")),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn dotenv_variables_are_found_with_or_without_export() {
+        let content = "# comment\nAPP_NAME=demo\n  export APP_PORT = 8080\nAPP_NAME_SUFFIX=x\n";
+        assert_eq!(find_dotenv_line(content, "APP_NAME"), Some(2));
+        assert_eq!(find_dotenv_line(content, "APP_PORT"), Some(3));
+        assert_eq!(find_dotenv_line(content, "APP_NAME_SUFFIX"), Some(4));
+        assert_eq!(find_dotenv_line(content, "APP"), None);
+    }
+}
@@ -1,7 +1,36 @@
 use config::Case::Snake;
 use config::{Config, ConfigError, Environment};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+/// The bootstrap file looked up in the working directory when no location is set.
+pub const DEFAULT_BOOTSTRAP_LOCATION: &str = "./bootstrap";
+
+/// The environment variable that sets the location of the bootstrap file.
+pub const BOOTSTRAP_LOCATION_ENV: &str = "BOOTSTRAP_LOCATION";
+
+/// The command-line option that sets the location of the bootstrap file, `--bootstrap <path>` or
+/// `--bootstrap=<path>`.
+pub const BOOTSTRAP_LOCATION_OPTION: &str = "bootstrap";
+
+/// The extensions tried by priority for a bootstrap location without extension.
+const BOOTSTRAP_FILE_EXTENSIONS: [&str; 4] = ["toml", "yaml", "yml", "json"];
+
+/// No bootstrap file was found at the location set explicitly.
+#[derive(Debug)]
+pub struct BootstrapFileNotFoundError {
+    pub location: String,
+}
+
+impl Display for BootstrapFileNotFoundError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "No bootstrap file found at {}", self.location)
+    }
+}
+
+impl Error for BootstrapFileNotFoundError {}
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 /// 启动配置属性结构体
@@ -60,6 +89,9 @@ pub struct DiscoveryProperties {
     pub server: ServerProperties,
     pub host: Option<HostProperties>,
     pub health: Option<HealthProperties>,
+    /// Fail the startup when the application can not be registered, it is logged otherwise.
+    #[serde(default)]
+    pub fail_fast: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -114,22 +146,56 @@ impl Default for BootstrapProperties {
 }
 
 impl BootstrapProperties {
+    /// Read the bootstrap file found at `path`, the default properties if there is none.
     pub fn read_from_path(path: &str) -> Result<BootstrapProperties, ConfigError> {
-        if Path::new(path).exists() {
-            let builder = Config::builder()
-                .add_source(config::File::with_name(path))
-                .add_source(
-                    Environment::default()
-                        .separator("_")
-                        .convert_case(Snake)
-                        .try_parsing(true),
-                );
-            let config = builder.build()?;
-            let properties = config.try_deserialize::<BootstrapProperties>()?;
-            Ok(properties)
-        } else {
-            Ok(BootstrapProperties::default())
+        match Self::find_file(path) {
+            Some(file) => Self::read_from_file(&file),
+            None => Ok(BootstrapProperties::default()),
+        }
+    }
+
+    /// Read the bootstrap file found at `path`, which must exist.
+    pub fn read_from_required_path(
+        path: &str,
+    ) -> Result<BootstrapProperties, Box<dyn Error + Send + Sync>> {
+        let file = Self::find_file(path).ok_or_else(|| BootstrapFileNotFoundError {
+            location: path.to_string(),
+        })?;
+        Ok(Self::read_from_file(&file)?)
+    }
+
+    /// The file at `path`, or for a path without extension or a directory, the first
+    /// `toml`, `yaml`, `yml` or `json` file named `path` or `path/bootstrap`.
+    pub fn find_file(path: &str) -> Option<PathBuf> {
+        let path = Path::new(path);
+        if path.is_file() {
+            return Some(path.to_path_buf());
         }
+        let base = if path.is_dir() {
+            path.join("bootstrap")
+        } else if path.extension().is_none() {
+            path.to_path_buf()
+        } else {
+            return None;
+        };
+        BOOTSTRAP_FILE_EXTENSIONS
+            .iter()
+            .map(|extension| base.with_extension(extension))
+            .find(|file| file.is_file())
+    }
+
+    /// The format of the file follows its extension.
+    fn read_from_file(file: &Path) -> Result<BootstrapProperties, ConfigError> {
+        let builder = Config::builder()
+            .add_source(config::File::from(file))
+            .add_source(
+                Environment::default()
+                    .separator("_")
+                    .convert_case(Snake)
+                    .try_parsing(true),
+            );
+        let config = builder.build()?;
+        config.try_deserialize::<BootstrapProperties>()
     }
 
     pub fn get_application_name(&self) -> String {
@@ -140,3 +206,82 @@ impl BootstrapProperties {
         self.application.port.unwrap_or(0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const YAML: &str = r#"
+application:
+  name: yaml
+  port: 8080
+  config:
+    activate:
+      profiles: [default]
+logger:
+  enabled: false
+  level: info
+  file: yaml.log
+  log_dir: ./logs
+"#;
+
+    const JSON: &str = r#"{
+  "application": {
+    "name": "json",
+    "config": { "activate": { "profiles": ["default", "dev"] } }
+  },
+  "logger": { "enabled": false, "level": "debug", "file": "json.log", "log_dir": "./logs" }
+}"#;
+
+    #[test]
+    fn yaml_and_json_bootstrap_files_are_read() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("bootstrap.yaml"), YAML).unwrap();
+        fs::write(dir.path().join("service.json"), JSON).unwrap();
+
+        let yaml =
+            BootstrapProperties::read_from_required_path(dir.path().to_str().unwrap()).unwrap();
+        let json = BootstrapProperties::read_from_required_path(
+            dir.path().join("service").to_str().unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(yaml.application.name, "yaml");
+        assert_eq!(yaml.application.port, Some(8080));
+        assert_eq!(json.application.name, "json");
+        assert_eq!(
+            json.application.config.activate.profiles,
+            vec!["default", "dev"]
+        );
+        assert_eq!(json.logger.level, "debug");
+    }
+
+    #[test]
+    fn a_toml_file_is_found_before_the_other_formats() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("bootstrap.yaml"), YAML).unwrap();
+        fs::write(dir.path().join("bootstrap.toml"), "").unwrap();
+
+        assert_eq!(
+            BootstrapProperties::find_file(dir.path().to_str().unwrap()),
+            Some(dir.path().join("bootstrap.toml"))
+        );
+    }
+
+    #[test]
+    fn a_missing_required_file_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let location = dir.path().join("missing.toml");
+        let location = location.to_str().unwrap();
+
+        let Err(error) = BootstrapProperties::read_from_required_path(location) else {
+            panic!("the bootstrap file does not exist");
+        };
+
+        let error = error.downcast_ref::<BootstrapFileNotFoundError>().unwrap();
+        assert_eq!(error.location, location);
+        // only a location that is not set falls back to the default properties
+        assert!(BootstrapProperties::read_from_path(location).is_ok());
+    }
+}
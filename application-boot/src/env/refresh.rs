@@ -0,0 +1,380 @@
+use crate::env::configuration::{
+    get_remote_config_keys, Configuration, ConfigurationResolver, CLOUD_PROPERTY_SOURCE_NAME,
+    CONFIG_PROPERTY_SOURCE_NAME, PROFILE_CONFIG_PROPERTY_SOURCE_NAME,
+    SYSTEM_ENVIRONMENT_PROPERTY_SOURCE_NAME,
+};
+use crate::env::properties::{CloudConfigProperties, CloudProperties};
+use application_beans::factory::bean_factory::BeanFactory;
+use application_beans::factory::configuration_properties::rebind_configuration_properties;
+use application_beans::factory::disposable_bean::DisposableBean;
+use application_context::context::application_context::APPLICATION_CONTEXT;
+use application_context::context::application_event::EnvironmentChangedEvent;
+use application_core::env::configuration_properties::parse_duration;
+use application_core::env::environment::{
+    ApplicationEnvironment, ConfigurableEnvironment, Environment,
+};
+use application_core::env::property::PropertySource;
+use application_core::env::property_resolver::PropertyResolver;
+use async_trait::async_trait;
+use axum::http::StatusCode;
+use axum::Json;
+use config::{Config, Map, Source, Value, ValueKind};
+use consulrs::api::features::{Blocking, FeaturesBuilder};
+use consulrs::api::kv::requests::ReadRawKeyRequest;
+use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+use consulrs::error::ClientError;
+use consulrs::kv;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tokio::{spawn, time};
+use tracing::{error, info};
+
+/// The property sources rebuilt by a full refresh, the others are set in code.
+pub const REFRESHABLE_PROPERTY_SOURCE_NAMES: [&str; 4] = [
+    SYSTEM_ENVIRONMENT_PROPERTY_SOURCE_NAME,
+    CLOUD_PROPERTY_SOURCE_NAME,
+    PROFILE_CONFIG_PROPERTY_SOURCE_NAME,
+    CONFIG_PROPERTY_SOURCE_NAME,
+];
+
+/// How long a Consul blocking query waits for a change of its key.
+const CONSUL_WATCH_WAIT: &str = "55s";
+
+/// Rebuilds the property sources of the environment of the application context, rebinds the
+/// refresh-scoped configurations and publishes an [`EnvironmentChangedEvent`].
+///
+/// Registered as `dyn DisposableBean`, the watchers stop when the application context is closed.
+#[derive(Default)]
+pub struct ContextRefresher {
+    /// Refreshes run one at a time.
+    lock: Mutex<()>,
+    watchers: std::sync::Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl ContextRefresher {
+    pub const BEAN_NAME: &'static str = "contextRefresher";
+
+    /// Rebuild all the refreshable property sources, the changed keys are returned.
+    pub async fn refresh(&self) -> Result<Vec<String>, Box<dyn Error + Send + Sync>> {
+        self.refresh_property_sources(&REFRESHABLE_PROPERTY_SOURCE_NAMES)
+            .await
+    }
+
+    /// Rebuild the property sources named `names` the environment has, the changed keys are
+    /// returned.
+    pub async fn refresh_property_sources(
+        &self,
+        names: &[&str],
+    ) -> Result<Vec<String>, Box<dyn Error + Send + Sync>> {
+        let _guard = self.lock.lock().await;
+        let application_context = APPLICATION_CONTEXT.read().await.clone();
+
+        // the sources are read from a copy, the environment is not locked while Consul is read
+        let environment = application_context.get_environment().await.clone();
+        let mut property_sources = vec![];
+        for name in names {
+            if !environment.get_property_sources().contains(name) {
+                continue;
+            }
+            let source = read_property_source(name, &environment)
+                .await
+                .map_err(|e| e.to_string())?;
+            property_sources.push(PropertySource {
+                name: name.to_string(),
+                source,
+            });
+        }
+        let before = get_properties(&environment);
+        let after = {
+            let mut environment = application_context.get_environment_mut().await;
+            for property_source in property_sources {
+                let name = property_source.name.clone();
+                environment
+                    .get_property_sources_mut()
+                    .replace(&name, property_source)?;
+            }
+            get_properties(&environment)
+        };
+
+        let keys = get_changed_keys(&before, &after);
+        if keys.is_empty() {
+            return Ok(keys);
+        }
+        info!("Refreshed the environment, changed keys {:?}", keys);
+        let rebound = {
+            let environment = application_context.get_environment().await;
+            rebind_configuration_properties(&environment, application_context.get_bean_factory())
+        };
+        application_context
+            .publish_event(Arc::new(EnvironmentChangedEvent { keys: keys.clone() }))
+            .await?;
+        rebound?;
+        Ok(keys)
+    }
+
+    /// Watch the config files and the Consul keys of the environment of the application
+    /// context, if `application.config.refresh.enabled` is set.
+    pub async fn start_watching(self: &Arc<Self>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let application_context = APPLICATION_CONTEXT.read().await.clone();
+        let environment = application_context.get_environment().await;
+        if !environment
+            .get_property::<bool>("application.config.refresh.enabled")
+            .unwrap_or(false)
+        {
+            return Ok(());
+        }
+        let interval = environment
+            .get_property::<String>("application.config.refresh.interval")
+            .unwrap_or("5s".to_string());
+        let interval = parse_duration(&interval)?;
+        let last_modified = get_config_file_times(&environment);
+        let mut watchers = vec![spawn(watch_config_files(
+            self.clone(),
+            last_modified,
+            interval,
+        ))];
+
+        let cloud_config = environment
+            .get_property::<CloudProperties>("application.cloud")
+            .and_then(|cloud| cloud.config)
+            .filter(|cloud_config| cloud_config.enabled);
+        if let Some(cloud_config) = cloud_config {
+            let application_name = environment
+                .get_property::<String>("application.name")
+                .unwrap_or_default();
+            let client = Arc::new(create_consul_client(&cloud_config)?);
+            let keys =
+                get_remote_config_keys(&environment.get_active_profiles(), &application_name);
+            for (_, key) in keys {
+                watchers.push(spawn(watch_consul_key(
+                    self.clone(),
+                    client.clone(),
+                    key,
+                    interval,
+                )));
+            }
+        }
+        self.watchers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .extend(watchers);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DisposableBean for ContextRefresher {
+    /// Stop watching the config files and the Consul keys.
+    async fn destroy(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let watchers: Vec<_> = self
+            .watchers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .drain(..)
+            .collect();
+        for watcher in watchers {
+            watcher.abort();
+        }
+        Ok(())
+    }
+}
+
+/// `POST /actuator/refresh`, responds with the changed keys.
+pub async fn refresh_endpoint() -> Result<Json<Vec<String>>, (StatusCode, String)> {
+    let application_context = APPLICATION_CONTEXT.read().await.clone();
+    let refresher = application_context
+        .get_bean_factory()
+        .get_bean::<ContextRefresher>()
+        .ok_or_else(|| {
+            let message = "No context refresher is registered".to_string();
+            (StatusCode::INTERNAL_SERVER_ERROR, message)
+        })?;
+    match refresher.refresh().await {
+        Ok(keys) => Ok(Json(keys)),
+        Err(e) => {
+            let message = format!("Refresh failed, {}", e);
+            error!("{}", message);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, message))
+        }
+    }
+}
+
+async fn read_property_source(
+    name: &str,
+    environment: &ApplicationEnvironment,
+) -> Result<Config, Box<dyn Error>> {
+    match name {
+        SYSTEM_ENVIRONMENT_PROPERTY_SOURCE_NAME => Configuration::read_system_environment(),
+        CLOUD_PROPERTY_SOURCE_NAME => {
+            Configuration::read_remote_config_from_environment(environment).await
+        }
+        PROFILE_CONFIG_PROPERTY_SOURCE_NAME => {
+            Configuration::read_profile_config_from_environment(environment)
+        }
+        CONFIG_PROPERTY_SOURCE_NAME => {
+            Configuration::read_native_config_from_environment(environment)
+        }
+        _ => Err(format!("Property source '{}' can not be refreshed", name).into()),
+    }
+}
+
+/// The value of every key of the property sources of `environment`, by precedence.
+fn get_properties(environment: &ApplicationEnvironment) -> BTreeMap<String, String> {
+    let mut properties = BTreeMap::new();
+    for property_source in environment.get_property_sources().get_sources() {
+        if let Ok(values) = property_source.source.collect() {
+            flatten(&values, "", &mut properties);
+        }
+    }
+    properties
+}
+
+/// Add the leaves of `values` under their dotted key, the keys set already are kept.
+fn flatten(values: &Map<String, Value>, prefix: &str, properties: &mut BTreeMap<String, String>) {
+    for (name, value) in values {
+        let key = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{}.{}", prefix, name)
+        };
+        match &value.kind {
+            ValueKind::Table(table) => flatten(table, &key, properties),
+            _ => {
+                properties.entry(key).or_insert_with(|| value.to_string());
+            }
+        }
+    }
+}
+
+/// The keys added, removed or changed, sorted.
+fn get_changed_keys(
+    before: &BTreeMap<String, String>,
+    after: &BTreeMap<String, String>,
+) -> Vec<String> {
+    let mut keys: Vec<String> = before
+        .iter()
+        .filter(|(key, value)| after.get(*key) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .chain(
+            after
+                .keys()
+                .filter(|key| !before.contains_key(*key))
+                .cloned(),
+        )
+        .collect();
+    keys.sort();
+    keys
+}
+
+/// Poll the modification time of the base and profile config files every `interval`, a
+/// file changed, created or removed refreshes their property sources.
+async fn watch_config_files(
+    refresher: Arc<ContextRefresher>,
+    mut last_modified: Vec<(PathBuf, Option<SystemTime>)>,
+    interval: Duration,
+) {
+    loop {
+        time::sleep(interval).await;
+        let modified = {
+            let application_context = APPLICATION_CONTEXT.read().await.clone();
+            let environment = application_context.get_environment().await;
+            get_config_file_times(&environment)
+        };
+        if modified == last_modified {
+            continue;
+        }
+        last_modified = modified;
+        let names = [
+            PROFILE_CONFIG_PROPERTY_SOURCE_NAME,
+            CONFIG_PROPERTY_SOURCE_NAME,
+        ];
+        if let Err(e) = refresher.refresh_property_sources(&names).await {
+            error!("Refresh of the config files failed, {}", e);
+        }
+    }
+}
+
+fn get_config_file_times(
+    environment: &ApplicationEnvironment,
+) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let mut config_files = Configuration::get_base_config_files(environment);
+    config_files.extend(Configuration::get_profile_config_files(environment));
+    config_files
+        .into_iter()
+        .map(|config_file| {
+            let modified = fs::metadata(&config_file)
+                .and_then(|metadata| metadata.modified())
+                .ok();
+            (PathBuf::from(config_file), modified)
+        })
+        .collect()
+}
+
+fn create_consul_client(
+    cloud_config: &CloudConfigProperties,
+) -> Result<ConsulClient, Box<dyn Error + Send + Sync>> {
+    let settings = ConsulClientSettingsBuilder::default()
+        .address(&cloud_config.address)
+        .token(cloud_config.token.clone().unwrap_or_default())
+        .build()?;
+    Ok(ConsulClient::new(settings)?)
+}
+
+/// Watch `key` with Consul blocking queries, a change, creation or removal of the key
+/// refreshes the cloud property source. Failed queries are retried every `interval`.
+async fn watch_consul_key(
+    refresher: Arc<ContextRefresher>,
+    client: Arc<ConsulClient>,
+    key: String,
+    interval: Duration,
+) {
+    // the index of the last response, 0 if the key does not exist
+    let mut index: Option<u64> = None;
+    loop {
+        let features = FeaturesBuilder::default()
+            .blocking(Blocking {
+                index: index.unwrap_or(0),
+                wait: Some(CONSUL_WATCH_WAIT.to_string()),
+            })
+            .build();
+        let mut request = ReadRawKeyRequest::builder();
+        if let (Some(_), Ok(features)) = (index, features) {
+            request.features(features);
+        }
+        let changed = match kv::raw(client.as_ref(), &key, Some(&mut request)).await {
+            Ok(response) => {
+                let new_index = response
+                    .index
+                    .and_then(|index| index.parse::<u64>().ok())
+                    .unwrap_or(0);
+                // the index goes back when Consul is restored, start over
+                let changed = index.is_some_and(|index| index != new_index);
+                index = Some(new_index);
+                changed
+            }
+            Err(ClientError::APIError { code: 404, .. }) => {
+                let changed = index.is_some_and(|index| index != 0);
+                index = Some(0);
+                time::sleep(interval).await;
+                changed
+            }
+            Err(e) => {
+                error!("Watch of the consul key '{}' failed, {}", key, e);
+                time::sleep(interval).await;
+                false
+            }
+        };
+        if changed {
+            let names = [CLOUD_PROPERTY_SOURCE_NAME];
+            if let Err(e) = refresher.refresh_property_sources(&names).await {
+                error!("Refresh of the consul key '{}' failed, {}", key, e);
+            }
+        }
+    }
+}
@@ -1,18 +1,40 @@
+use crate::env::refresh::refresh_endpoint;
 use application_beans::factory::bean_factory::ConfigurableBeanFactory;
+use application_beans::factory::condition::{on_property, Condition};
 use application_context::context::application_context::ConfigurableApplicationContext;
 use application_core::env::property_resolver::PropertyResolver;
 use application_web_actuator::health::health_check;
 use async_trait::async_trait;
-use axum::routing::get;
+use axum::routing::{get, post};
 use axum::Router;
-use tracing::info;
+use std::any::type_name;
+use std::sync::Arc;
+use tracing::{error, info};
 
 pub trait ApplicationContextInitializer: Send + Sync {
-    fn initialize(&self, application_context: &Box<dyn ConfigurableApplicationContext>);
+    fn initialize(&self, application_context: &Arc<dyn ConfigurableApplicationContext>);
+
+    fn get_name(&self) -> &'static str {
+        type_name::<Self>()
+    }
+
+    /// The initializer is only applied if all conditions match.
+    fn conditions(&self) -> Vec<Box<dyn Condition>> {
+        vec![]
+    }
 }
 #[async_trait]
 pub trait ServletContextInitializer: Send + Sync {
     fn initialize(&self, router: Router) -> Router;
+
+    fn get_name(&self) -> &'static str {
+        type_name::<Self>()
+    }
+
+    /// The initializer is only applied if all conditions match.
+    fn conditions(&self) -> Vec<Box<dyn Condition>> {
+        vec![]
+    }
 }
 
 pub struct ActuatorRouterInitializer;
@@ -21,6 +43,29 @@ impl ServletContextInitializer for ActuatorRouterInitializer {
     fn initialize(&self, router: Router) -> Router {
         router.route("/actuator/health", get(health_check))
     }
+
+    fn conditions(&self) -> Vec<Box<dyn Condition>> {
+        vec![Box::new(
+            on_property("management.endpoint.health.enabled", "true").match_if_missing(),
+        )]
+    }
+}
+
+/// `POST /actuator/refresh` refreshes the environment, see
+/// [`crate::env::refresh::ContextRefresher`]. Enabled by `management.endpoint.refresh.enabled`.
+pub struct RefreshEndpointRouterInitializer;
+
+impl ServletContextInitializer for RefreshEndpointRouterInitializer {
+    fn initialize(&self, router: Router) -> Router {
+        router.route("/actuator/refresh", post(refresh_endpoint))
+    }
+
+    fn conditions(&self) -> Vec<Box<dyn Condition>> {
+        vec![Box::new(on_property(
+            "management.endpoint.refresh.enabled",
+            "true",
+        ))]
+    }
 }
 
 pub struct ContextIdApplicationContextInitializer {}
@@ -31,13 +76,15 @@ pub struct ContextId {
 }
 
 impl ApplicationContextInitializer for ContextIdApplicationContextInitializer {
-    fn initialize(&self, application_context: &Box<dyn ConfigurableApplicationContext>) {
+    fn initialize(&self, application_context: &Arc<dyn ConfigurableApplicationContext>) {
         let environment = application_context.get_environment_blocking();
         let id = environment
             .get_property::<String>("application.name")
             .unwrap_or("application".to_string());
         let context_id = ContextId { id };
         info!("Initializer set {:?}", context_id);
-        application_context.get_bean_factory().set(context_id);
+        if let Err(e) = application_context.get_bean_factory().set(context_id) {
+            error!("Initializer set ContextId failed, {}", e);
+        }
     }
 }
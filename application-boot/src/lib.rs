@@ -1,5 +1,7 @@
 pub mod application;
 pub mod application_banner;
+pub mod application_builder;
+pub mod application_error;
 pub mod application_listener;
 pub mod application_properties;
 mod application_run_listener;
@@ -7,9 +9,12 @@ pub mod application_run_listeners;
 pub mod bootstrap;
 pub mod cloud;
 pub mod context;
+pub mod diagnostics;
 pub mod env;
 pub mod initializer;
 pub mod logging;
 mod metrics;
+pub mod runner;
+pub mod shutdown_hook;
 pub mod web;
 pub mod web_application_type;
@@ -0,0 +1,480 @@
+use application_context::context::application_context::ConfigurableApplicationContext;
+use async_trait::async_trait;
+pub use inventory::submit;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+
+/// The arguments the application was started with.
+///
+/// `--name=value` and `--name value` are options with a value, `--name` followed by another
+/// option, by `--` or by nothing is an option without value. The other arguments and all the
+/// arguments after `--` are non-option arguments, so a non-option argument following an option
+/// without value must come after `--`.
+#[derive(Debug, Clone, Default)]
+pub struct ApplicationArguments {
+    source_args: Vec<String>,
+    options: BTreeMap<String, Vec<String>>,
+    non_option_args: Vec<String>,
+}
+
+impl ApplicationArguments {
+    /// Parse `args`, without the program name. An option without name and an option followed
+    /// by an argument starting with `-`, e.g. `--offset -1`, are rejected, the value must be
+    /// joined with `=`.
+    pub fn new(args: Vec<String>) -> Result<Self, ArgumentsError> {
+        Self::parse(args, true)
+    }
+
+    /// Parse `args` like [`ApplicationArguments::new`], without rejecting any argument: an
+    /// option without valid name is a non-option argument and an option followed by an argument
+    /// starting with `-` has no value.
+    pub fn lenient(args: Vec<String>) -> Self {
+        Self::parse(args, false).expect("the lenient parsing accepts every argument")
+    }
+
+    fn parse(args: Vec<String>, strict: bool) -> Result<Self, ArgumentsError> {
+        let mut options: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut non_option_args = vec![];
+        let mut iter = args.iter().peekable();
+        while let Some(arg) = iter.next() {
+            if arg == "--" {
+                non_option_args.extend(iter.cloned());
+                break;
+            }
+            let Some(option) = arg.strip_prefix("--") else {
+                non_option_args.push(arg.clone());
+                continue;
+            };
+            let (name, value) = match option.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => {
+                    if let Some(next) = iter.peek().filter(|next| is_ambiguous_value(next)) {
+                        if strict {
+                            return Err(ArgumentsError::new(
+                                arg,
+                                format!("write {}={} if {} is its value", arg, next, next),
+                            ));
+                        }
+                    }
+                    (option, iter.next_if(|next| !next.starts_with('-')).cloned())
+                }
+            };
+            if name.is_empty() || name.starts_with('-') || name.contains(char::is_whitespace) {
+                if strict {
+                    return Err(ArgumentsError::new(arg, "the option name is invalid"));
+                }
+                non_option_args.push(arg.clone());
+                continue;
+            }
+            let values = options.entry(name.to_string()).or_default();
+            values.extend(value);
+        }
+        Ok(ApplicationArguments {
+            source_args: args,
+            options,
+            non_option_args,
+        })
+    }
+
+    pub fn get_source_args(&self) -> &[String] {
+        &self.source_args
+    }
+
+    pub fn get_option_names(&self) -> Vec<&str> {
+        self.options.keys().map(|name| name.as_str()).collect()
+    }
+
+    pub fn contains_option(&self, name: &str) -> bool {
+        self.options.contains_key(name)
+    }
+
+    /// The values of the option, empty for `--name`, `None` if it was not given.
+    pub fn get_option_values(&self, name: &str) -> Option<&[String]> {
+        self.options.get(name).map(|values| values.as_slice())
+    }
+
+    pub fn get_non_option_args(&self) -> &[String] {
+        &self.non_option_args
+    }
+}
+
+/// An argument that can not be parsed as an option.
+#[derive(Debug)]
+pub struct ArgumentsError {
+    pub argument: String,
+    pub reason: String,
+}
+
+impl ArgumentsError {
+    fn new(argument: &str, reason: impl Into<String>) -> Self {
+        ArgumentsError {
+            argument: argument.to_string(),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl Display for ArgumentsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid argument '{}', {}", self.argument, self.reason)
+    }
+}
+
+impl Error for ArgumentsError {}
+
+/// An argument starting with a single `-` after an option, a negative number or a short option.
+fn is_ambiguous_value(arg: &str) -> bool {
+    arg.starts_with('-') && !arg.starts_with("--")
+}
+
+/// Runs once the application has started, with the parsed arguments of the application.
+///
+/// Registered with `RustApplicationBuilder::application_runner` or `submit_application_runner!`.
+#[async_trait]
+pub trait ApplicationRunner: Send + Sync {
+    /// Runners with a lower order run first.
+    fn order(&self) -> i32 {
+        0
+    }
+
+    async fn run(
+        &self,
+        application_context: &Arc<dyn ConfigurableApplicationContext>,
+        args: &ApplicationArguments,
+    ) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+/// Runs once the application has started, with the raw arguments of the application.
+///
+/// Registered with `RustApplicationBuilder::command_line_runner` or `submit_command_line_runner!`.
+#[async_trait]
+pub trait CommandLineRunner: Send + Sync {
+    /// Runners with a lower order run first.
+    fn order(&self) -> i32 {
+        0
+    }
+
+    async fn run(
+        &self,
+        application_context: &Arc<dyn ConfigurableApplicationContext>,
+        args: &[String],
+    ) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+inventory::collect!(&'static dyn ApplicationRunner);
+
+inventory::collect!(&'static dyn CommandLineRunner);
+
+/// auto register
+#[macro_export]
+macro_rules! submit_application_runner {
+    ($ty:ident) => {
+        ::application_boot::runner::submit! {
+            &$ty as &dyn ::application_boot::runner::ApplicationRunner
+        }
+    };
+}
+
+/// auto register
+#[macro_export]
+macro_rules! submit_command_line_runner {
+    ($ty:ident) => {
+        ::application_boot::runner::submit! {
+            &$ty as &dyn ::application_boot::runner::CommandLineRunner
+        }
+    };
+}
+
+/// A runner of either kind, so that both kinds run by one order.
+pub(crate) enum Runner<'a> {
+    Application(&'a dyn ApplicationRunner),
+    CommandLine(&'a dyn CommandLineRunner),
+}
+
+impl Runner<'_> {
+    fn order(&self) -> i32 {
+        match self {
+            Runner::Application(runner) => runner.order(),
+            Runner::CommandLine(runner) => runner.order(),
+        }
+    }
+
+    async fn run(
+        &self,
+        application_context: &Arc<dyn ConfigurableApplicationContext>,
+        args: &ApplicationArguments,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        match self {
+            Runner::Application(runner) => runner.run(application_context, args).await,
+            Runner::CommandLine(runner) => {
+                runner
+                    .run(application_context, args.get_source_args())
+                    .await
+            }
+        }
+    }
+}
+
+/// Run `runners` and the submitted runners by order, stop at the first failure.
+pub(crate) async fn call_runners(
+    mut runners: Vec<Runner<'_>>,
+    application_context: &Arc<dyn ConfigurableApplicationContext>,
+    args: &ApplicationArguments,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    runners.extend(
+        inventory::iter::<&dyn ApplicationRunner>
+            .into_iter()
+            .map(|runner| Runner::Application(*runner)),
+    );
+    runners.extend(
+        inventory::iter::<&dyn CommandLineRunner>
+            .into_iter()
+            .map(|runner| Runner::CommandLine(*runner)),
+    );
+    runners.sort_by_key(|runner| runner.order());
+    for runner in runners {
+        runner.run(application_context, args).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use application_context::context::application_context::GenericApplicationContext;
+    use std::sync::Mutex;
+
+    type Calls = Arc<Mutex<Vec<String>>>;
+
+    /// Records its name with the non-option arguments, fails if `fails` is set.
+    struct Recording {
+        name: &'static str,
+        order: i32,
+        fails: bool,
+        calls: Calls,
+    }
+
+    impl Recording {
+        fn new(name: &'static str, order: i32, calls: &Calls) -> Self {
+            Recording {
+                name,
+                order,
+                fails: false,
+                calls: calls.clone(),
+            }
+        }
+
+        fn record(&self, args: &[String]) -> Result<(), Box<dyn Error + Send + Sync>> {
+            let call = format!("{} {}", self.name, args.join(" "));
+            self.calls.lock().unwrap().push(call);
+            if self.fails {
+                return Err(format!("{} failed", self.name).into());
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl ApplicationRunner for Recording {
+        fn order(&self) -> i32 {
+            self.order
+        }
+
+        async fn run(
+            &self,
+            _application_context: &Arc<dyn ConfigurableApplicationContext>,
+            args: &ApplicationArguments,
+        ) -> Result<(), Box<dyn Error + Send + Sync>> {
+            self.record(args.get_non_option_args())
+        }
+    }
+
+    /// A [`Recording`] called with the raw arguments.
+    struct CommandLine(Recording);
+
+    #[async_trait]
+    impl CommandLineRunner for CommandLine {
+        fn order(&self) -> i32 {
+            self.0.order
+        }
+
+        async fn run(
+            &self,
+            _application_context: &Arc<dyn ConfigurableApplicationContext>,
+            args: &[String],
+        ) -> Result<(), Box<dyn Error + Send + Sync>> {
+            self.0.record(args)
+        }
+    }
+
+    #[tokio::test]
+    async fn both_kinds_of_runners_run_by_order() {
+        let calls = Calls::default();
+        let second = Recording::new("second", 0, &calls);
+        let last = Recording::new("last", 2, &calls);
+        let first = CommandLine(Recording::new("first", -1, &calls));
+        let third = CommandLine(Recording::new("third", 0, &calls));
+        let fourth = CommandLine(Recording::new("fourth", 1, &calls));
+        let runners = vec![
+            Runner::Application(&last),
+            Runner::Application(&second),
+            Runner::CommandLine(&first),
+            Runner::CommandLine(&third),
+            Runner::CommandLine(&fourth),
+        ];
+        let application_context: Arc<dyn ConfigurableApplicationContext> =
+            Arc::new(GenericApplicationContext::default());
+
+        call_runners(
+            runners,
+            &application_context,
+            &parse(&["--debug=true", "input.csv"]).unwrap(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                "first --debug=true input.csv",
+                "second input.csv",
+                "third --debug=true input.csv",
+                "fourth --debug=true input.csv",
+                "last input.csv",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn a_failed_runner_stops_the_next_ones() {
+        let calls = Calls::default();
+        let first = Recording::new("first", 0, &calls);
+        let mut failing = Recording::new("failing", 1, &calls);
+        failing.fails = true;
+        let skipped = CommandLine(Recording::new("skipped", 2, &calls));
+        let runners = vec![
+            Runner::CommandLine(&skipped),
+            Runner::Application(&failing),
+            Runner::Application(&first),
+        ];
+        let application_context: Arc<dyn ConfigurableApplicationContext> =
+            Arc::new(GenericApplicationContext::default());
+
+        let error = call_runners(runners, &application_context, &parse(&[]).unwrap())
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.to_string(), "failing failed");
+        assert_eq!(*calls.lock().unwrap(), vec!["first ", "failing "]);
+    }
+
+    fn parse(args: &[&str]) -> Result<ApplicationArguments, ArgumentsError> {
+        ApplicationArguments::new(args.iter().map(|arg| arg.to_string()).collect())
+    }
+
+    #[test]
+    fn options_take_a_joined_or_a_following_value() {
+        let args = parse(&["--bootstrap", "/etc/app", "--application.port=8080"]).unwrap();
+
+        assert_eq!(
+            args.get_option_values("bootstrap"),
+            Some(&["/etc/app".to_string()][..])
+        );
+        assert_eq!(
+            args.get_option_values("application.port"),
+            Some(&["8080".to_string()][..])
+        );
+        assert!(args.get_non_option_args().is_empty());
+    }
+
+    #[test]
+    fn options_followed_by_an_option_or_nothing_have_no_value() {
+        let args = parse(&["--debug", "--trace=true", "--verbose"]).unwrap();
+
+        assert_eq!(args.get_option_values("debug"), Some(&[][..]));
+        assert_eq!(args.get_option_values("verbose"), Some(&[][..]));
+        assert_eq!(args.get_option_names(), vec!["debug", "trace", "verbose"]);
+    }
+
+    #[test]
+    fn repeated_options_keep_their_values_in_order() {
+        let args = parse(&["--profile=dev", "--profile", "local"]).unwrap();
+
+        assert_eq!(
+            args.get_option_values("profile"),
+            Some(&["dev".to_string(), "local".to_string()][..])
+        );
+    }
+
+    #[test]
+    fn values_may_contain_equal_signs_and_be_empty() {
+        let args = parse(&["--filter=a=b", "--name="]).unwrap();
+
+        assert_eq!(
+            args.get_option_values("filter"),
+            Some(&["a=b".to_string()][..])
+        );
+        assert_eq!(args.get_option_values("name"), Some(&["".to_string()][..]));
+    }
+
+    #[test]
+    fn arguments_after_the_separator_are_not_options() {
+        let args = parse(&["input.csv", "--debug", "--", "--output", "out.csv"]).unwrap();
+
+        assert_eq!(args.get_option_names(), vec!["debug"]);
+        assert_eq!(args.get_option_values("debug"), Some(&[][..]));
+        assert_eq!(
+            args.get_non_option_args(),
+            &[
+                "input.csv".to_string(),
+                "--output".to_string(),
+                "out.csv".to_string()
+            ]
+        );
+        assert_eq!(args.get_source_args().len(), 5);
+    }
+
+    #[test]
+    fn values_starting_with_a_dash_must_be_joined() {
+        let error = parse(&["--offset", "-1"]).unwrap_err();
+        assert_eq!(error.argument, "--offset");
+        assert!(error.to_string().contains("--offset=-1"));
+
+        let args = parse(&["--offset=-1"]).unwrap();
+        assert_eq!(
+            args.get_option_values("offset"),
+            Some(&["-1".to_string()][..])
+        );
+    }
+
+    #[test]
+    fn options_without_a_valid_name_are_rejected() {
+        for arg in ["--=value", "---name", "--my name=value"] {
+            let error = parse(&[arg]).unwrap_err();
+            assert_eq!(error.argument, arg);
+        }
+    }
+
+    #[test]
+    fn the_lenient_parsing_keeps_the_rejected_arguments() {
+        let args = ApplicationArguments::lenient(
+            ["--offset", "-1", "---name", "--=value", "--debug"]
+                .iter()
+                .map(|arg| arg.to_string())
+                .collect(),
+        );
+
+        assert_eq!(args.get_option_names(), vec!["debug", "offset"]);
+        assert_eq!(args.get_option_values("offset"), Some(&[][..]));
+        assert_eq!(
+            args.get_non_option_args(),
+            &[
+                "-1".to_string(),
+                "---name".to_string(),
+                "--=value".to_string()
+            ]
+        );
+    }
+}
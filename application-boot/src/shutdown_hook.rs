@@ -0,0 +1,110 @@
+use application_beans::factory::bean_definition::BoxFuture;
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::timeout;
+use tracing::warn;
+
+/// The time the shutdown hooks are given to complete by default.
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub type ShutdownHook = Box<dyn Fn() -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Async hooks run when the application receives a termination signal, before the application
+/// context is closed.
+pub struct ShutdownHooks {
+    hooks: Vec<ShutdownHook>,
+    timeout: Duration,
+}
+
+impl Default for ShutdownHooks {
+    fn default() -> Self {
+        ShutdownHooks {
+            hooks: Default::default(),
+            timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+        }
+    }
+}
+
+impl ShutdownHooks {
+    pub fn add<F, Fut>(&mut self, hook: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.hooks.push(Box::new(move || Box::pin(hook())));
+    }
+
+    /// Set the time all the hooks together are given to complete.
+    pub fn set_timeout(&mut self, shutdown_timeout: Duration) {
+        self.timeout = shutdown_timeout;
+    }
+
+    /// Run the hooks in registration order, the hooks still running after the timeout are dropped.
+    pub async fn run(&self) {
+        if self.hooks.is_empty() {
+            return;
+        }
+        let shutdown_timeout = self.timeout;
+        let run_hooks = async {
+            for hook in self.hooks.iter() {
+                hook().await;
+            }
+        };
+        if timeout(shutdown_timeout, run_hooks).await.is_err() {
+            warn!(
+                "Shutdown hooks did not complete in {:?}, shutting down",
+                shutdown_timeout
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    fn record(
+        ran: &Arc<Mutex<Vec<&'static str>>>,
+        name: &'static str,
+        delay: Duration,
+    ) -> impl Fn() -> BoxFuture<'static, ()> + Send + Sync + 'static {
+        let ran = ran.clone();
+        move || {
+            let ran = ran.clone();
+            Box::pin(async move {
+                tokio::time::sleep(delay).await;
+                ran.lock().unwrap().push(name);
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn hooks_run_in_registration_order() {
+        let ran = Arc::new(Mutex::new(vec![]));
+        let mut hooks = ShutdownHooks::default();
+        hooks.add(record(&ran, "slower", Duration::from_millis(20)));
+        hooks.add(record(&ran, "faster", Duration::ZERO));
+
+        hooks.run().await;
+
+        assert_eq!(*ran.lock().unwrap(), vec!["slower", "faster"]);
+    }
+
+    #[tokio::test]
+    async fn a_slow_hook_is_cut_off_by_the_timeout() {
+        let ran = Arc::new(Mutex::new(vec![]));
+        let mut hooks = ShutdownHooks::default();
+        hooks.add(record(&ran, "fast", Duration::ZERO));
+        hooks.add(record(&ran, "slow", Duration::from_secs(30)));
+        hooks.add(record(&ran, "after", Duration::ZERO));
+        hooks.set_timeout(Duration::from_millis(100));
+
+        let started = Instant::now();
+        hooks.run().await;
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(*ran.lock().unwrap(), vec!["fast"]);
+    }
+}
@@ -1,58 +1,96 @@
 use application_beans::factory::bean_factory::{
-    BeanFactory, DefaultListableBeanFactory, ListableBeanFactory,
+    BeanFactory, ConfigurableBeanFactory, DefaultListableBeanFactory, ListableBeanFactory,
 };
+use application_beans::factory::component::ENVIRONMENT_BEAN_NAME;
 use application_context::context::application_context::{
-    ApplicationContext, ConfigurableApplicationContext,
+    ApplicationContext, ConfigurableApplicationContext, ContextHierarchy,
 };
 use application_context::context::application_event::{
     ApplicationEvent, ApplicationEventPublisher,
 };
+use application_context::context::application_event_multi_caster::{
+    ApplicationEventError, SimpleApplicationEventMultiCaster,
+};
 use application_core::env::environment::{ApplicationEnvironment, EnvironmentCapable};
 use application_core::env::property_resolver::PropertyResolver;
 use application_web::server::{AxumServer, WebServer};
 use async_std::task::block_on;
 use async_trait::async_trait;
 use std::any::Any;
+use std::error::Error;
 use std::hash::{DefaultHasher, Hash, Hasher};
 use std::ptr::addr_of;
 use std::sync::Arc;
 use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use tracing::info;
 
 #[async_trait]
 pub trait WebServerApplicationContext: ConfigurableApplicationContext {
-    async fn get_web_server(&self) -> RwLockReadGuard<'_, Box<dyn WebServer>>;
+    async fn get_web_server(&self) -> RwLockReadGuard<'_, Arc<dyn WebServer>>;
 }
 
 pub struct ServletWebServerApplicationContext {
     environment: Arc<RwLock<ApplicationEnvironment>>,
     bean_factory: DefaultListableBeanFactory,
-    web_server: Arc<RwLock<Box<dyn WebServer>>>,
+    web_server: Arc<RwLock<Arc<dyn WebServer>>>,
+    hierarchy: ContextHierarchy,
+    event_multi_caster: SimpleApplicationEventMultiCaster,
 }
 
 impl Default for ServletWebServerApplicationContext {
     fn default() -> Self {
+        let environment: Arc<RwLock<ApplicationEnvironment>> = Default::default();
+        let bean_factory = DefaultListableBeanFactory::default();
+        // lets components read properties while they are created
+        let _ = bean_factory.set_as(ENVIRONMENT_BEAN_NAME, environment.clone());
         Self {
-            environment: Default::default(),
-            bean_factory: Default::default(),
-            web_server: Arc::new(RwLock::new(Box::new(AxumServer { port: 0 }))),
+            environment,
+            bean_factory,
+            web_server: Arc::new(RwLock::new(Arc::new(AxumServer::new(0)))),
+            hierarchy: Default::default(),
+            event_multi_caster: Default::default(),
         }
     }
 }
 
 impl BeanFactory for ServletWebServerApplicationContext {
-    fn get<T: 'static>(&self) -> &T {
+    fn get<T: ?Sized + 'static>(&self) -> &T {
         self.bean_factory.get::<T>()
     }
 
-    fn try_get<T: 'static>(&self) -> Option<&T> {
+    fn try_get<T: ?Sized + 'static>(&self) -> Option<&T> {
         self.bean_factory.try_get::<T>()
     }
+
+    fn get_bean<T: ?Sized + Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        self.bean_factory.get_bean::<T>()
+    }
+
+    fn get_named<T: ?Sized + Send + Sync + 'static>(&self, name: &str) -> Option<Arc<T>> {
+        self.bean_factory.get_named::<T>(name)
+    }
+
+    fn get_all<T: ?Sized + Send + Sync + 'static>(&self) -> Vec<Arc<T>> {
+        self.bean_factory.get_all::<T>()
+    }
+
+    fn contains_bean(&self, name: &str) -> bool {
+        self.bean_factory.contains_bean(name)
+    }
 }
 
 impl ListableBeanFactory for ServletWebServerApplicationContext {
     fn get_bean_definition_count(&self) -> usize {
         self.bean_factory.get_bean_definition_count()
     }
+
+    fn get_bean_names(&self) -> Vec<String> {
+        self.bean_factory.get_bean_names()
+    }
+
+    fn contains_bean_of_type<T: ?Sized + Send + Sync + 'static>(&self) -> bool {
+        self.bean_factory.contains_bean_of_type::<T>()
+    }
 }
 
 #[async_trait]
@@ -72,7 +110,14 @@ impl EnvironmentCapable for ServletWebServerApplicationContext {
 
 #[async_trait]
 impl ApplicationEventPublisher for ServletWebServerApplicationContext {
-    fn publish_event(&self, _event: Arc<Box<dyn ApplicationEvent>>) {}
+    async fn publish_event(
+        &self,
+        event: Arc<dyn ApplicationEvent>,
+    ) -> Result<(), ApplicationEventError> {
+        self.event_multi_caster
+            .multicast_event(event, &self.bean_factory)
+            .await
+    }
 }
 
 #[async_trait]
@@ -92,26 +137,66 @@ impl ApplicationContext for ServletWebServerApplicationContext {
     async fn set_environment(&self, environment: ApplicationEnvironment) {
         let mut application_environment = self.environment.write().await;
         *application_environment = environment;
+        self.hierarchy
+            .link_environment(&mut application_environment);
+    }
+    fn get_parent(&self) -> Option<Arc<dyn ConfigurableApplicationContext>> {
+        self.hierarchy.get_parent()
     }
 }
 
 #[async_trait]
 impl ConfigurableApplicationContext for ServletWebServerApplicationContext {
-    async fn refresh(&self) {
-        let port = self
-            .get_environment()
-            .await
-            .get_property::<u16>("application.port")
-            .unwrap();
-        let web_server = AxumServer { port };
+    async fn on_refresh(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        // a web server registered as bean replaces the default axum server
+        let web_server = match self.bean_factory.get_all::<dyn WebServer>().first() {
+            Some(web_server) => web_server.clone(),
+            None => {
+                let port = self
+                    .get_environment()
+                    .await
+                    .get_property::<u16>("application.port")
+                    .ok_or("Property application.port is not set")?;
+                let web_server: Arc<dyn WebServer> = Arc::new(AxumServer::new(port));
+                if let Err(e) = self
+                    .bean_factory
+                    .set_as::<dyn WebServer>("webServer", web_server.clone())
+                {
+                    info!("Register web server failed, {}", e);
+                }
+                web_server
+            }
+        };
         let mut application_web_server = self.web_server.write().await;
-        *application_web_server = Box::new(web_server);
+        *application_web_server = web_server;
+        Ok(())
+    }
+
+    fn get_event_multi_caster(&self) -> &SimpleApplicationEventMultiCaster {
+        &self.event_multi_caster
+    }
+
+    async fn set_parent(
+        self: Arc<Self>,
+        parent: Arc<dyn ConfigurableApplicationContext>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut environment = self.environment.write().await;
+        self.hierarchy
+            .set_parent(self.clone(), parent, &self.bean_factory, &mut environment)
+    }
+
+    fn add_child(&self, child: &Arc<dyn ConfigurableApplicationContext>) {
+        self.hierarchy.add_child(child);
+    }
+
+    fn get_children(&self) -> Vec<Arc<dyn ConfigurableApplicationContext>> {
+        self.hierarchy.get_children()
     }
 }
 
 #[async_trait]
 impl WebServerApplicationContext for ServletWebServerApplicationContext {
-    async fn get_web_server(&self) -> RwLockReadGuard<'_, Box<dyn WebServer>> {
+    async fn get_web_server(&self) -> RwLockReadGuard<'_, Arc<dyn WebServer>> {
         self.web_server.read().await
     }
 }
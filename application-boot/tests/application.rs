@@ -0,0 +1,163 @@
+mod common;
+
+use application_boot::application::{Application, RustApplication};
+use application_boot::application_error::ApplicationError;
+use application_boot::application_listener::ApplicationListener;
+use application_boot::runner::{ApplicationArguments, ApplicationRunner};
+use application_boot::web_application_type::WebApplicationType;
+use application_context::context::application_context::ConfigurableApplicationContext;
+use application_context::context::application_event::{
+    ApplicationEvenType, ApplicationEvent, ApplicationFailedEvent,
+};
+use async_trait::async_trait;
+use std::error::Error;
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+/// Keeps the non-option arguments the runners are called with.
+struct ArgumentsRunner(Arc<Mutex<Vec<String>>>);
+
+#[async_trait]
+impl ApplicationRunner for ArgumentsRunner {
+    async fn run(
+        &self,
+        _application_context: &Arc<dyn ConfigurableApplicationContext>,
+        args: &ApplicationArguments,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        *self.0.lock().unwrap() = args.get_non_option_args().to_vec();
+        Ok(())
+    }
+}
+
+/// Fails the application once it started.
+struct FailingRunner;
+
+#[async_trait]
+impl ApplicationRunner for FailingRunner {
+    async fn run(
+        &self,
+        _application_context: &Arc<dyn ConfigurableApplicationContext>,
+        _args: &ApplicationArguments,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        Err("the runner failed".into())
+    }
+}
+
+/// Keeps the errors of the failed events.
+struct FailedListener(Arc<Mutex<Vec<String>>>);
+
+#[async_trait]
+impl ApplicationListener for FailedListener {
+    fn is_support(&self, event: &dyn ApplicationEvent) -> bool {
+        event.get_event_type() == ApplicationEvenType::Failed
+    }
+
+    async fn on_application_event(
+        &self,
+        _application: &RustApplication,
+        event: &dyn ApplicationEvent,
+    ) -> Result<(), Box<dyn Error>> {
+        let event = event
+            .as_any()
+            .downcast_ref::<ApplicationFailedEvent>()
+            .unwrap();
+        self.0.lock().unwrap().push(event.error.to_string());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn a_failed_step_is_returned_and_published() {
+    let _lock = common::APPLICATION_LOCK.lock().await;
+    let dir = tempfile::tempdir().unwrap();
+    common::write_bootstrap(dir.path(), "failing");
+    let failures = Arc::new(Mutex::new(vec![]));
+
+    let error = common::builder("failing", dir.path().to_str().unwrap())
+        .application_runner(Box::new(FailingRunner))
+        .listener(Box::new(FailedListener(failures.clone())))
+        .build()
+        .run()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, ApplicationError::Runner(_)));
+    assert_eq!(*failures.lock().unwrap(), vec![error.to_string()]);
+    assert_eq!(
+        error.to_string(),
+        "Failed to run the application runners, the runner failed"
+    );
+}
+
+#[tokio::test]
+async fn the_web_server_is_stopped_when_a_runner_fails() {
+    let _lock = common::APPLICATION_LOCK.lock().await;
+    let dir = tempfile::tempdir().unwrap();
+    common::write_bootstrap(dir.path(), "web");
+    let port = TcpListener::bind("0.0.0.0:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+
+    let error = common::builder("web", dir.path().to_str().unwrap())
+        .web_application_type(WebApplicationType::WEB)
+        .default_property("application.port", port as i64)
+        .application_runner(Box::new(FailingRunner))
+        .build()
+        .run()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, ApplicationError::Runner(_)));
+    // the port is free again
+    TcpListener::bind(("0.0.0.0", port)).unwrap();
+}
+
+#[tokio::test]
+async fn a_missing_bootstrap_file_fails_before_the_listeners_are_created() {
+    let _lock = common::APPLICATION_LOCK.lock().await;
+    let dir = tempfile::tempdir().unwrap();
+    let failures = Arc::new(Mutex::new(vec![]));
+
+    let error = common::builder("missing", dir.path().to_str().unwrap())
+        .listener(Box::new(FailedListener(failures.clone())))
+        .build()
+        .run()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, ApplicationError::Bootstrap(_)));
+    assert!(failures.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn arguments_are_not_rejected_without_command_line_properties() {
+    let _lock = common::APPLICATION_LOCK.lock().await;
+    let dir = tempfile::tempdir().unwrap();
+    common::write_bootstrap(dir.path(), "arguments");
+    let location = dir.path().to_str().unwrap();
+
+    let error = common::builder("arguments", location)
+        .args(args(&["--offset", "-1", "input.csv"]))
+        .build()
+        .run()
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("--offset=-1"));
+
+    let non_option_args = Arc::new(Mutex::new(vec![]));
+    common::builder("arguments", location)
+        .args(args(&["--offset", "-1", "input.csv"]))
+        .add_command_line_properties(false)
+        .application_runner(Box::new(ArgumentsRunner(non_option_args.clone())))
+        .build()
+        .run()
+        .await
+        .unwrap();
+    assert_eq!(*non_option_args.lock().unwrap(), args(&["-1", "input.csv"]));
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use application_beans::factory::bean_factory::BeanFactory;
+use application_boot::application::Application;
+use application_boot::application_error::ApplicationError;
+use application_boot::bootstrap::default_bootstrap_context::DefaultBootstrapContext;
+use application_boot::env::properties::BOOTSTRAP_LOCATION_ENV;
+use application_boot::runner::{ApplicationArguments, ApplicationRunner};
+use application_context::context::application_context::ConfigurableApplicationContext;
+use async_trait::async_trait;
+use std::error::Error;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+
+/// Keeps the name of the application read from the bootstrap file.
+struct NameRunner(Arc<Mutex<String>>);
+
+#[async_trait]
+impl ApplicationRunner for NameRunner {
+    async fn run(
+        &self,
+        application_context: &Arc<dyn ConfigurableApplicationContext>,
+        _args: &ApplicationArguments,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let bootstrap_context = application_context
+            .get_bean_factory()
+            .get::<DefaultBootstrapContext>();
+        *self.0.lock().unwrap() = bootstrap_context
+            .get_bootstrap_properties()
+            .get_application_name();
+        Ok(())
+    }
+}
+
+/// A directory with the bootstrap file of the application `name`.
+fn bootstrap_dir(name: &str) -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    common::write_bootstrap(dir.path(), name);
+    dir
+}
+
+fn location(dir: &Path) -> &str {
+    dir.to_str().unwrap()
+}
+
+/// The name of the application run with `args` and the bootstrap location `builder`.
+async fn run(builder: &Path, args: Vec<String>) -> Result<String, ApplicationError> {
+    let name = Arc::new(Mutex::new(String::new()));
+    common::builder("location", location(builder))
+        .args(args)
+        .application_runner(Box::new(NameRunner(name.clone())))
+        .build()
+        .run()
+        .await?;
+    let name = name.lock().unwrap().clone();
+    Ok(name)
+}
+
+/// The environment variable is read by every application of the process, this is the only test
+/// of the binary.
+#[tokio::test]
+async fn the_option_wins_over_the_environment_variable_over_the_builder() {
+    let builder = bootstrap_dir("builder");
+    let environment = bootstrap_dir("environment");
+    let option = bootstrap_dir("option");
+    let option_arg = format!("--bootstrap={}", location(option.path()));
+
+    assert_eq!(run(builder.path(), vec![]).await.unwrap(), "builder");
+
+    std::env::set_var(BOOTSTRAP_LOCATION_ENV, location(environment.path()));
+    assert_eq!(run(builder.path(), vec![]).await.unwrap(), "environment");
+    assert_eq!(
+        run(builder.path(), vec![option_arg]).await.unwrap(),
+        "option"
+    );
+
+    // a location set explicitly must exist, the next one is not tried
+    let missing = environment.path().join("missing.toml");
+    std::env::set_var(BOOTSTRAP_LOCATION_ENV, location(&missing));
+    let error = run(builder.path(), vec![]).await.unwrap_err();
+    std::env::remove_var(BOOTSTRAP_LOCATION_ENV);
+
+    assert!(matches!(error, ApplicationError::Bootstrap(_)));
+    assert!(error.to_string().contains("missing.toml"));
+}
@@ -0,0 +1,57 @@
+//! The setup shared by the tests that run an application.
+#![allow(dead_code)]
+
+use application_boot::application::RustApplication;
+use application_boot::application_banner::BannerMode;
+use application_boot::application_builder::RustApplicationBuilder;
+use application_boot::bootstrap::initializer::ConsulBootstrapRegistryInitializer;
+use application_boot::logging::listener::{
+    LoggingApplicationListener, LoggingCleanApplicationListener,
+};
+use application_boot::web_application_type::WebApplicationType;
+use std::fs;
+use std::path::Path;
+use tokio::sync::Mutex;
+
+/// The application context is global, the applications of a test binary run one at a time.
+pub static APPLICATION_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// The content of a bootstrap file of the application `name`, with its config files and logs
+/// in `location`.
+pub fn bootstrap(name: &str, location: &str) -> String {
+    format!(
+        r#"
+[application]
+name = "{name}"
+[application.config]
+locations = ["{location}"]
+file_names = ["config.toml"]
+[application.config.activate]
+profiles = ["default"]
+[logger]
+enabled = false
+level = "info"
+file = "{name}.log"
+log_dir = "{location}"
+"#
+    )
+}
+
+/// Write the bootstrap file of the application `name` to `dir/bootstrap.toml`.
+pub fn write_bootstrap(dir: &Path, name: &str) {
+    let location = dir.to_str().unwrap();
+    fs::write(dir.join("bootstrap.toml"), bootstrap(name, location)).unwrap();
+}
+
+/// An application without web server, banner, Consul nor logging setup, reading its bootstrap
+/// file from `location`.
+pub fn builder(name: &str, location: &str) -> RustApplicationBuilder {
+    RustApplication::builder(name)
+        .web_application_type(WebApplicationType::NONE)
+        .bootstrap_location(location)
+        .banner_mode(BannerMode::Off)
+        .args(vec![])
+        .without_bootstrap_registry_initializer::<ConsulBootstrapRegistryInitializer>()
+        .without_listener::<LoggingApplicationListener>()
+        .without_listener::<LoggingCleanApplicationListener>()
+}
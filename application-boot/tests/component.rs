@@ -0,0 +1,34 @@
+use application_beans::factory::bean_factory::{BeanFactory, DefaultListableBeanFactory};
+use application_beans::factory::component::register_components;
+use application_web_macros::Component;
+use async_std::task::block_on;
+use std::sync::Arc;
+
+#[derive(Component)]
+struct UserService {
+    repository: Arc<UserRepository>,
+    #[bean("userRepository")]
+    named_repository: Arc<UserRepository>,
+    cache: Option<Arc<UserCache>>,
+}
+
+#[derive(Component)]
+#[component(lazy)]
+struct UserRepository;
+
+struct UserCache;
+
+#[test]
+fn component_fields_are_resolved_from_beans_created_first() {
+    let bean_factory = DefaultListableBeanFactory::default();
+    register_components(&bean_factory).unwrap();
+    block_on(bean_factory.preinstantiate_singletons()).unwrap();
+
+    let user_service = bean_factory
+        .get_named::<UserService>("userService")
+        .unwrap();
+    let repository = bean_factory.get_bean::<UserRepository>().unwrap();
+    assert!(Arc::ptr_eq(&user_service.repository, &repository));
+    assert!(Arc::ptr_eq(&user_service.named_repository, &repository));
+    assert!(user_service.cache.is_none());
+}
@@ -0,0 +1,148 @@
+use application_beans::factory::bean_factory::{BeanFactory, DefaultListableBeanFactory};
+use application_beans::factory::configuration_properties::{
+    bind_configuration_properties, Refreshable,
+};
+use application_core::env::configuration_properties::ConfigurationProperties;
+use application_core::env::environment::{ApplicationEnvironment, ConfigurableEnvironment};
+use application_core::env::property::PropertySource;
+use application_web_macros::ConfigurationProperties;
+use config::Config;
+use std::time::Duration;
+
+#[derive(ConfigurationProperties, Debug)]
+#[config(prefix = "app.mail")]
+struct MailProperties {
+    #[validate(non_empty)]
+    smtp_host: String,
+    #[config(default = 25)]
+    #[validate(range(min = 1, max = 65535))]
+    port: u16,
+    #[config(default = Duration::from_secs(30))]
+    #[validate(duration(max = "5m"))]
+    timeout: Duration,
+    #[config(default)]
+    #[validate(regex = "^[a-z]*$")]
+    protocol: String,
+    username: Option<String>,
+}
+
+#[derive(ConfigurationProperties)]
+#[config(prefix = "app.server", name = "server", refresh)]
+struct ServerProperties {
+    #[config(default = "localhost".to_string())]
+    host: String,
+}
+
+fn environment(properties: &[(&str, &str)]) -> ApplicationEnvironment {
+    let mut builder = Config::builder();
+    for (key, value) in properties {
+        builder = builder.set_override(*key, *value).unwrap();
+    }
+    let mut environment = ApplicationEnvironment::default();
+    environment.add_property_source(PropertySource {
+        name: "test".to_string(),
+        source: builder.build().unwrap(),
+    });
+    environment
+}
+
+#[test]
+fn fields_are_bound_from_their_relaxed_keys_or_defaults() {
+    let environment = environment(&[("app.mail.smtp-host", "smtp.example.com")]);
+
+    let mail = MailProperties::bind(&environment).unwrap();
+
+    assert_eq!(MailProperties::PREFIX, "app.mail");
+    assert_eq!(mail.smtp_host, "smtp.example.com");
+    assert_eq!(mail.port, 25);
+    assert_eq!(mail.timeout, Duration::from_secs(30));
+    assert_eq!(mail.protocol, "");
+    assert_eq!(mail.username, None);
+}
+
+#[test]
+fn properties_override_the_defaults() {
+    let environment = environment(&[
+        ("app.mail.smtpHost", "smtp.example.com"),
+        ("app.mail.port", "2525"),
+        ("app.mail.timeout", "1m"),
+        ("app.mail.protocol", "smtps"),
+        ("app.mail.username", "admin"),
+    ]);
+
+    let mail = MailProperties::bind(&environment).unwrap();
+
+    assert_eq!(mail.port, 2525);
+    assert_eq!(mail.timeout, Duration::from_secs(60));
+    assert_eq!(mail.protocol, "smtps");
+    assert_eq!(mail.username.as_deref(), Some("admin"));
+}
+
+#[test]
+fn every_invalid_field_is_reported_at_once() {
+    let environment = environment(&[
+        ("app.mail.smtp_host", ""),
+        ("app.mail.port", "0"),
+        ("app.mail.timeout", "10m"),
+        ("app.mail.protocol", "SMTP"),
+    ]);
+
+    let error = MailProperties::bind(&environment).unwrap_err();
+
+    let reported: Vec<(&str, &str)> = error
+        .invalid_properties
+        .iter()
+        .map(|property| (property.key.as_str(), property.reason.as_str()))
+        .collect();
+    assert_eq!(
+        reported,
+        vec![
+            ("app.mail.smtp_host", "must not be empty"),
+            ("app.mail.port", "must be between 1 and 65535"),
+            ("app.mail.timeout", "must be at most 300s"),
+            ("app.mail.protocol", "must match \"^[a-z]*$\""),
+        ]
+    );
+}
+
+#[test]
+fn missing_and_unconvertible_fields_are_reported() {
+    let environment = environment(&[("app.mail.port", "smtp"), ("app.mail.timeout", "soon")]);
+
+    let error = MailProperties::bind(&environment).unwrap_err();
+
+    let reported: Vec<(&str, Option<&str>)> = error
+        .invalid_properties
+        .iter()
+        .map(|property| (property.key.as_str(), property.value.as_deref()))
+        .collect();
+    assert_eq!(
+        reported,
+        vec![
+            ("app.mail.smtp_host", None),
+            ("app.mail.port", Some("smtp")),
+            ("app.mail.timeout", Some("soon")),
+        ]
+    );
+    assert_eq!(
+        error.invalid_properties[0].reason,
+        "is required but not set"
+    );
+}
+
+#[test]
+fn configurations_are_registered_under_their_name() {
+    let environment = environment(&[("app.mail.smtp-host", "smtp.example.com")]);
+    let bean_factory = DefaultListableBeanFactory::default();
+
+    bind_configuration_properties(&environment, &bean_factory).unwrap();
+
+    let mail = bean_factory
+        .get_named::<MailProperties>("mailProperties")
+        .unwrap();
+    assert_eq!(mail.smtp_host, "smtp.example.com");
+    let server = bean_factory
+        .get_named::<Refreshable<ServerProperties>>("server")
+        .unwrap();
+    assert_eq!(server.get().host, "localhost");
+}
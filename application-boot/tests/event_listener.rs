@@ -0,0 +1,72 @@
+use application_beans::factory::bean_factory::BeanFactory;
+use application_beans::factory::component::register_components;
+use application_context::context::application_context::{
+    ApplicationContext, GenericApplicationContext,
+};
+use application_context::context::application_event::{
+    ApplicationEvent, ApplicationEventListener, ApplicationEventPublisher,
+};
+use application_web_macros::event_listener;
+use std::any::Any;
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+
+struct OrderCreated {
+    id: u32,
+}
+
+impl ApplicationEvent for OrderCreated {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+struct OrderCancelled;
+
+impl ApplicationEvent for OrderCancelled {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+static CREATED: Mutex<Vec<u32>> = Mutex::new(vec![]);
+static CANCELLED: Mutex<usize> = Mutex::new(0);
+
+#[event_listener]
+async fn on_order_created(event: &OrderCreated) -> Result<(), Box<dyn Error + Send + Sync>> {
+    CREATED.lock().unwrap().push(event.id);
+    Ok(())
+}
+
+#[event_listener(name = "cancelledOrderListener")]
+async fn on_order_cancelled(_event: &OrderCancelled) -> Result<(), Box<dyn Error + Send + Sync>> {
+    *CANCELLED.lock().unwrap() += 1;
+    Ok(())
+}
+
+#[tokio::test]
+async fn event_listeners_receive_the_events_of_their_type() {
+    let application_context = GenericApplicationContext::default();
+    let bean_factory = application_context.get_bean_factory();
+    register_components(bean_factory).unwrap();
+    assert!(bean_factory
+        .get_named::<dyn ApplicationEventListener>("on_order_created")
+        .is_some());
+    assert!(bean_factory
+        .get_named::<dyn ApplicationEventListener>("cancelledOrderListener")
+        .is_some());
+
+    application_context
+        .publish_event(Arc::new(OrderCreated { id: 7 }))
+        .await
+        .unwrap();
+    assert_eq!(*CREATED.lock().unwrap(), vec![7]);
+    assert_eq!(*CANCELLED.lock().unwrap(), 0);
+
+    application_context
+        .publish_event(Arc::new(OrderCancelled))
+        .await
+        .unwrap();
+    assert_eq!(*CREATED.lock().unwrap(), vec![7]);
+    assert_eq!(*CANCELLED.lock().unwrap(), 1);
+}
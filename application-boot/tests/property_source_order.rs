@@ -0,0 +1,126 @@
+use application_boot::application::{Application, RustApplication};
+use application_boot::application_banner::BannerMode;
+use application_boot::bootstrap::initializer::ConsulBootstrapRegistryInitializer;
+use application_boot::logging::listener::{
+    LoggingApplicationListener, LoggingCleanApplicationListener,
+};
+use application_boot::runner::{ApplicationArguments, ApplicationRunner};
+use application_boot::web_application_type::WebApplicationType;
+use application_context::context::application_context::ConfigurableApplicationContext;
+use application_core::env::environment::ConfigurableEnvironment;
+use application_core::env::property_resolver::PropertyResolver;
+use async_trait::async_trait;
+use std::error::Error;
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+const KEYS: [&str; 5] = [
+    "order.cli",
+    "order.env",
+    "order.profile",
+    "order.base",
+    "order.default",
+];
+
+/// The property source names and the values of [`KEYS`] of the started application.
+#[derive(Default)]
+struct EnvironmentCapture {
+    names: Mutex<Vec<String>>,
+    values: Mutex<Vec<Option<String>>>,
+}
+
+struct CaptureRunner(Arc<EnvironmentCapture>);
+
+#[async_trait]
+impl ApplicationRunner for CaptureRunner {
+    async fn run(
+        &self,
+        application_context: &Arc<dyn ConfigurableApplicationContext>,
+        _args: &ApplicationArguments,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let environment = application_context.get_environment().await;
+        *self.0.names.lock().unwrap() = environment
+            .get_property_sources()
+            .get_names()
+            .into_iter()
+            .map(String::from)
+            .collect();
+        *self.0.values.lock().unwrap() = KEYS
+            .iter()
+            .map(|key| environment.get_property::<String>(key))
+            .collect();
+        Ok(())
+    }
+}
+
+/// Each key is set by its layer and every layer below it, so it resolves to its own layer.
+#[tokio::test]
+async fn built_in_property_sources_by_precedence() {
+    let dir = tempfile::tempdir().unwrap();
+    let location = dir.path().to_str().unwrap();
+    fs::write(
+        dir.path().join("bootstrap.toml"),
+        format!(
+            r#"
+[application]
+name = "order"
+[application.config]
+locations = ["{location}"]
+file_names = ["config.toml"]
+[application.config.activate]
+profiles = ["default", "dev"]
+[logger]
+enabled = false
+level = "info"
+file = "order.log"
+log_dir = "{location}"
+"#
+        ),
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("config.toml"),
+        "[order]\ncli = \"base\"\nenv = \"base\"\nprofile = \"base\"\nbase = \"base\"\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("config-dev.toml"),
+        "[order]\ncli = \"profile\"\nenv = \"profile\"\nprofile = \"profile\"\n",
+    )
+    .unwrap();
+    std::env::set_var("ORDER_CLI", "env");
+    std::env::set_var("ORDER_ENV", "env");
+
+    let capture = Arc::new(EnvironmentCapture::default());
+    let mut builder = RustApplication::builder("order")
+        .web_application_type(WebApplicationType::NONE)
+        .bootstrap_location(location)
+        .banner_mode(BannerMode::Off)
+        .args(vec!["--order.cli=cli".to_string()])
+        .without_bootstrap_registry_initializer::<ConsulBootstrapRegistryInitializer>()
+        .without_listener::<LoggingApplicationListener>()
+        .without_listener::<LoggingCleanApplicationListener>()
+        .application_runner(Box::new(CaptureRunner(capture.clone())));
+    for key in KEYS {
+        builder = builder.default_property(key, "default");
+    }
+    builder.build().run().await.unwrap();
+
+    assert_eq!(
+        *capture.names.lock().unwrap(),
+        vec![
+            "commandLineArgs",
+            "systemEnvironment",
+            "cloudProperties",
+            "profileConfigProperties",
+            "configProperties",
+            "defaultProperties",
+        ]
+    );
+    assert_eq!(
+        *capture.values.lock().unwrap(),
+        ["cli", "env", "profile", "base", "default"]
+            .map(|value| Some(value.to_string()))
+            .to_vec()
+    );
+}
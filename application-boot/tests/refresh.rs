@@ -0,0 +1,149 @@
+use application_beans::factory::bean_factory::BeanFactory;
+use application_beans::factory::configuration_properties::Refreshable;
+use application_boot::application::{Application, RustApplication};
+use application_boot::application_banner::BannerMode;
+use application_boot::bootstrap::initializer::ConsulBootstrapRegistryInitializer;
+use application_boot::env::refresh::ContextRefresher;
+use application_boot::logging::listener::{
+    LoggingApplicationListener, LoggingCleanApplicationListener,
+};
+use application_boot::runner::{ApplicationArguments, ApplicationRunner};
+use application_boot::web_application_type::WebApplicationType;
+use application_context::context::application_context::ConfigurableApplicationContext;
+use application_context::context::application_event::{
+    EnvironmentChangedEvent, EventListener, EventListenerAdapter,
+};
+use application_core::env::property_resolver::PropertyResolver;
+use application_web_macros::ConfigurationProperties;
+use async_trait::async_trait;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+#[derive(ConfigurationProperties)]
+#[config(prefix = "greeting", refresh)]
+struct GreetingProperties {
+    message: String,
+}
+
+#[derive(Default)]
+struct RefreshCapture {
+    messages: Mutex<Vec<String>>,
+    keys: Mutex<Vec<String>>,
+    events: Mutex<Vec<Vec<String>>>,
+}
+
+struct EnvironmentChangedListener(Arc<RefreshCapture>);
+
+#[async_trait]
+impl EventListener<EnvironmentChangedEvent> for EnvironmentChangedListener {
+    async fn on_event(
+        &self,
+        event: &EnvironmentChangedEvent,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.0.events.lock().unwrap().push(event.keys.clone());
+        Ok(())
+    }
+}
+
+/// Changes the config file and refreshes the environment.
+struct RefreshRunner {
+    config_file: PathBuf,
+    capture: Arc<RefreshCapture>,
+}
+
+#[async_trait]
+impl ApplicationRunner for RefreshRunner {
+    async fn run(
+        &self,
+        application_context: &Arc<dyn ConfigurableApplicationContext>,
+        _args: &ApplicationArguments,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        application_context.add_application_listener(Arc::new(EventListenerAdapter::new(
+            EnvironmentChangedListener(self.capture.clone()),
+        )));
+        let bean_factory = application_context.get_bean_factory();
+        let greeting = bean_factory
+            .get_named::<Refreshable<GreetingProperties>>("greetingProperties")
+            .unwrap();
+        let refresher = bean_factory.get_bean::<ContextRefresher>().unwrap();
+        let mut messages = vec![greeting.get().message.clone()];
+
+        fs::write(
+            &self.config_file,
+            "[greeting]\nmessage = \"after\"\nsuffix = \"!\"\n",
+        )?;
+        let keys = refresher.refresh().await?;
+        messages.push(greeting.get().message.clone());
+        messages.extend(
+            application_context
+                .get_environment()
+                .await
+                .get_property::<String>("greeting.message"),
+        );
+        // nothing changed since
+        assert!(refresher.refresh().await?.is_empty());
+
+        *self.capture.messages.lock().unwrap() = messages;
+        *self.capture.keys.lock().unwrap() = keys;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn refresh_rebinds_refresh_scoped_configurations() {
+    let dir = tempfile::tempdir().unwrap();
+    let location = dir.path().to_str().unwrap();
+    fs::write(
+        dir.path().join("bootstrap.toml"),
+        format!(
+            r#"
+[application]
+name = "greeting"
+[application.config]
+locations = ["{location}"]
+file_names = ["config.toml"]
+[application.config.activate]
+profiles = ["default"]
+[logger]
+enabled = false
+level = "info"
+file = "greeting.log"
+log_dir = "{location}"
+"#
+        ),
+    )
+    .unwrap();
+    let config_file = dir.path().join("config.toml");
+    fs::write(&config_file, "[greeting]\nmessage = \"before\"\n").unwrap();
+
+    let capture = Arc::new(RefreshCapture::default());
+    RustApplication::builder("greeting")
+        .web_application_type(WebApplicationType::NONE)
+        .bootstrap_location(location)
+        .banner_mode(BannerMode::Off)
+        .args(vec![])
+        .without_bootstrap_registry_initializer::<ConsulBootstrapRegistryInitializer>()
+        .without_listener::<LoggingApplicationListener>()
+        .without_listener::<LoggingCleanApplicationListener>()
+        .application_runner(Box::new(RefreshRunner {
+            config_file,
+            capture: capture.clone(),
+        }))
+        .build()
+        .run()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        *capture.messages.lock().unwrap(),
+        ["before", "after", "after"]
+    );
+    let keys = vec![
+        "greeting.message".to_string(),
+        "greeting.suffix".to_string(),
+    ];
+    assert_eq!(*capture.keys.lock().unwrap(), keys);
+    assert_eq!(*capture.events.lock().unwrap(), vec![keys]);
+}
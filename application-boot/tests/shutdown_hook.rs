@@ -0,0 +1,56 @@
+mod common;
+
+use application_boot::application::Application;
+use application_boot::runner::{ApplicationArguments, ApplicationRunner};
+use application_context::context::application_context::ConfigurableApplicationContext;
+use async_trait::async_trait;
+use std::error::Error;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::signal::unix::{signal, SignalKind};
+
+/// Sends SIGTERM to the process and waits far longer than the test takes.
+struct TerminatingRunner;
+
+#[async_trait]
+impl ApplicationRunner for TerminatingRunner {
+    async fn run(
+        &self,
+        _application_context: &Arc<dyn ConfigurableApplicationContext>,
+        _args: &ApplicationArguments,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let status = Command::new("kill")
+            .args(["-TERM", &std::process::id().to_string()])
+            .status()?;
+        assert!(status.success());
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        Ok(())
+    }
+}
+
+/// The termination signal is latched for the process, this is the only test of the binary.
+#[tokio::test(flavor = "multi_thread")]
+async fn hooks_run_on_sigterm_without_web_server() {
+    // the handler is installed before the signal is sent, SIGTERM would kill the test otherwise
+    let _terminate = signal(SignalKind::terminate()).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    common::write_bootstrap(dir.path(), "terminated");
+    let ran = Arc::new(Mutex::new(vec![]));
+
+    let hook_ran = ran.clone();
+    let started = Instant::now();
+    common::builder("terminated", dir.path().to_str().unwrap())
+        .application_runner(Box::new(TerminatingRunner))
+        .shutdown_hook(move || {
+            let ran = hook_ran.clone();
+            async move { ran.lock().unwrap().push("hook") }
+        })
+        .build()
+        .run()
+        .await
+        .unwrap();
+
+    assert!(started.elapsed() < Duration::from_secs(30));
+    assert_eq!(*ran.lock().unwrap(), vec!["hook"]);
+}
@@ -1,15 +1,28 @@
-use crate::context::application_event::{ApplicationEvent, ApplicationEventPublisher};
+use crate::context::application_event::{
+    ApplicationEvent, ApplicationEventListener, ApplicationEventPublisher, ContextClosedEvent,
+    ContextClosingEvent, ContextRefreshedEvent,
+};
+use crate::context::application_event_multi_caster::{
+    ApplicationEventError, SimpleApplicationEventMultiCaster,
+};
+use crate::context::lifecycle::{start_lifecycle_beans, stop_lifecycle_beans};
 use application_beans::factory::bean_factory::{
-    BeanFactory, DefaultListableBeanFactory, ListableBeanFactory,
+    BeanFactory, ConfigurableBeanFactory, DefaultListableBeanFactory, ListableBeanFactory,
+    ParentBeanFactory,
+};
+use application_beans::factory::bean_post_processor::BeanPostProcessor;
+use application_beans::factory::component::ENVIRONMENT_BEAN_NAME;
+use application_core::env::environment::{
+    ApplicationEnvironment, ConfigurableEnvironment, EnvironmentCapable,
 };
-use application_core::env::environment::{ApplicationEnvironment, EnvironmentCapable};
 use application_core::env::property_resolver::PropertyResolver;
 use async_std::task::block_on;
 use async_trait::async_trait;
 use std::any::Any;
+use std::error::Error;
 use std::hash::{DefaultHasher, Hash, Hasher};
 use std::ptr::addr_of;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, OnceLock, Weak};
 use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 #[async_trait]
@@ -23,33 +36,210 @@ pub trait ApplicationContext: EnvironmentCapable + ApplicationEventPublisher + S
     }
     fn get_bean_factory(&self) -> &DefaultListableBeanFactory;
     async fn set_environment(&self, environment: ApplicationEnvironment);
+    /// The context beans and properties missing from this one are looked up in.
+    fn get_parent(&self) -> Option<Arc<dyn ConfigurableApplicationContext>> {
+        None
+    }
 }
 #[async_trait]
 pub trait ConfigurableApplicationContext: ApplicationContext {
-    async fn refresh(&self) {}
+    /// Evaluate the bean definition conditions, instantiate the non-lazy bean definitions,
+    /// start the lifecycle beans and publish a [`ContextRefreshedEvent`].
+    async fn refresh(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let bean_factory = self.get_bean_factory();
+        {
+            let environment = self.get_environment().await;
+            bean_factory.evaluate_conditions(&environment);
+        }
+        self.on_refresh().await?;
+        bean_factory.preinstantiate_singletons().await?;
+        start_lifecycle_beans(bean_factory).await?;
+        let context_id = self.get_id();
+        self.publish_event(Arc::new(ContextRefreshedEvent { context_id }))
+            .await?;
+        Ok(())
+    }
+    /// Context specific work done by refresh before the singletons are instantiated.
+    async fn on_refresh(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        Ok(())
+    }
     async fn after_refresh(&self) {}
+    /// Post processors added before refresh also apply to the beans created by bean definitions.
+    fn add_bean_post_processor(&self, post_processor: Arc<dyn BeanPostProcessor>) {
+        self.get_bean_factory()
+            .add_bean_post_processor(post_processor);
+    }
+    fn get_event_multi_caster(&self) -> &SimpleApplicationEventMultiCaster;
+    fn add_application_listener(&self, listener: Arc<dyn ApplicationEventListener>) {
+        self.get_event_multi_caster().add_listener(listener);
+    }
+    /// Make this context a child of `parent`: lookups fall through to `parent`, which closes
+    /// this context before itself.
+    async fn set_parent(
+        self: Arc<Self>,
+        _parent: Arc<dyn ConfigurableApplicationContext>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        Err("This application context does not support a parent".into())
+    }
+    /// Called by [`ConfigurableApplicationContext::set_parent`] of `child`.
+    fn add_child(&self, _child: &Arc<dyn ConfigurableApplicationContext>) {}
+    fn get_children(&self) -> Vec<Arc<dyn ConfigurableApplicationContext>> {
+        vec![]
+    }
+    /// Publish a [`ContextClosingEvent`], close the child contexts, stop the lifecycle beans
+    /// and destroy the singletons in reverse of their start order, then publish a
+    /// [`ContextClosedEvent`]. Every step runs even if one fails, the first failure is returned.
+    async fn close(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let context_id = self.get_id();
+        let mut result: Result<(), Box<dyn Error + Send + Sync>> = self
+            .publish_event(Arc::new(ContextClosingEvent {
+                context_id: context_id.clone(),
+            }))
+            .await
+            .map_err(|e| e.into());
+        for child in self.get_children().iter().rev() {
+            if let Err(e) = child.close().await {
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+        let bean_factory = self.get_bean_factory();
+        let stopped = stop_lifecycle_beans(bean_factory).await;
+        let destroyed = bean_factory.destroy_singletons().await;
+        let closed = self
+            .publish_event(Arc::new(ContextClosedEvent { context_id }))
+            .await;
+        result
+            .and(stopped)
+            .and(destroyed.map_err(|e| e.into()))
+            .and(closed.map_err(|e| e.into()))
+    }
 }
 
+/// The parent and the child contexts of an application context.
 #[derive(Default)]
+pub struct ContextHierarchy {
+    parent: OnceLock<Arc<dyn ConfigurableApplicationContext>>,
+    children: Mutex<Vec<Weak<dyn ConfigurableApplicationContext>>>,
+}
+
+struct ParentApplicationContext(Arc<dyn ConfigurableApplicationContext>);
+
+impl ParentBeanFactory for ParentApplicationContext {
+    fn get_bean_factory(&self) -> &DefaultListableBeanFactory {
+        self.0.get_bean_factory()
+    }
+}
+
+impl ContextHierarchy {
+    pub fn get_parent(&self) -> Option<Arc<dyn ConfigurableApplicationContext>> {
+        self.parent.get().cloned()
+    }
+
+    /// Set the parent, link the bean factory and the environment of the context to it and
+    /// add the context to the children of the parent.
+    pub fn set_parent(
+        &self,
+        context: Arc<dyn ConfigurableApplicationContext>,
+        parent: Arc<dyn ConfigurableApplicationContext>,
+        bean_factory: &DefaultListableBeanFactory,
+        environment: &mut ApplicationEnvironment,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.parent
+            .set(parent.clone())
+            .map_err(|_| "The parent context is already set")?;
+        bean_factory.set_parent_bean_factory(Arc::new(ParentApplicationContext(parent.clone())))?;
+        self.link_environment(environment);
+        parent.add_child(&context);
+        Ok(())
+    }
+
+    /// Resolve the properties missing from `environment` from the environment of the parent.
+    pub fn link_environment(&self, environment: &mut ApplicationEnvironment) {
+        let parent_environment = self.get_parent().and_then(|parent| {
+            parent
+                .get_bean_factory()
+                .get_named::<RwLock<ApplicationEnvironment>>(ENVIRONMENT_BEAN_NAME)
+        });
+        if let Some(parent_environment) = parent_environment {
+            environment.set_parent(parent_environment);
+        }
+    }
+
+    pub fn add_child(&self, child: &Arc<dyn ConfigurableApplicationContext>) {
+        let mut children = self.children.lock().unwrap();
+        children.retain(|child| child.strong_count() > 0);
+        children.push(Arc::downgrade(child));
+    }
+
+    /// The child contexts still alive, in the order they were added.
+    pub fn get_children(&self) -> Vec<Arc<dyn ConfigurableApplicationContext>> {
+        let children = self.children.lock().unwrap();
+        children.iter().filter_map(Weak::upgrade).collect()
+    }
+}
+
 pub struct GenericApplicationContext {
     environment: Arc<RwLock<ApplicationEnvironment>>,
     bean_factory: DefaultListableBeanFactory,
+    hierarchy: ContextHierarchy,
+    event_multi_caster: SimpleApplicationEventMultiCaster,
+}
+
+impl Default for GenericApplicationContext {
+    fn default() -> Self {
+        let environment: Arc<RwLock<ApplicationEnvironment>> = Default::default();
+        let bean_factory = DefaultListableBeanFactory::default();
+        // lets components read properties while they are created
+        let _ = bean_factory.set_as(ENVIRONMENT_BEAN_NAME, environment.clone());
+        Self {
+            environment,
+            bean_factory,
+            hierarchy: Default::default(),
+            event_multi_caster: Default::default(),
+        }
+    }
 }
 
 impl BeanFactory for GenericApplicationContext {
-    fn get<T: 'static>(&self) -> &T {
+    fn get<T: ?Sized + 'static>(&self) -> &T {
         self.bean_factory.get::<T>()
     }
 
-    fn try_get<T: 'static>(&self) -> Option<&T> {
+    fn try_get<T: ?Sized + 'static>(&self) -> Option<&T> {
         self.bean_factory.try_get::<T>()
     }
+
+    fn get_bean<T: ?Sized + Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        self.bean_factory.get_bean::<T>()
+    }
+
+    fn get_named<T: ?Sized + Send + Sync + 'static>(&self, name: &str) -> Option<Arc<T>> {
+        self.bean_factory.get_named::<T>(name)
+    }
+
+    fn get_all<T: ?Sized + Send + Sync + 'static>(&self) -> Vec<Arc<T>> {
+        self.bean_factory.get_all::<T>()
+    }
+
+    fn contains_bean(&self, name: &str) -> bool {
+        self.bean_factory.contains_bean(name)
+    }
 }
 
 impl ListableBeanFactory for GenericApplicationContext {
     fn get_bean_definition_count(&self) -> usize {
         self.bean_factory.get_bean_definition_count()
     }
+
+    fn get_bean_names(&self) -> Vec<String> {
+        self.bean_factory.get_bean_names()
+    }
+
+    fn contains_bean_of_type<T: ?Sized + Send + Sync + 'static>(&self) -> bool {
+        self.bean_factory.contains_bean_of_type::<T>()
+    }
 }
 
 #[async_trait]
@@ -69,7 +259,14 @@ impl EnvironmentCapable for GenericApplicationContext {
 
 #[async_trait]
 impl ApplicationEventPublisher for GenericApplicationContext {
-    fn publish_event(&self, _event: Arc<Box<dyn ApplicationEvent>>) {}
+    async fn publish_event(
+        &self,
+        event: Arc<dyn ApplicationEvent>,
+    ) -> Result<(), ApplicationEventError> {
+        self.event_multi_caster
+            .multicast_event(event, &self.bean_factory)
+            .await
+    }
 }
 
 #[async_trait]
@@ -92,13 +289,147 @@ impl ApplicationContext for GenericApplicationContext {
     async fn set_environment(&self, environment: ApplicationEnvironment) {
         let mut application_environment = self.environment.write().await;
         *application_environment = environment;
+        self.hierarchy
+            .link_environment(&mut application_environment);
+    }
+
+    fn get_parent(&self) -> Option<Arc<dyn ConfigurableApplicationContext>> {
+        self.hierarchy.get_parent()
     }
 }
 
-impl ConfigurableApplicationContext for GenericApplicationContext {}
+#[async_trait]
+impl ConfigurableApplicationContext for GenericApplicationContext {
+    fn get_event_multi_caster(&self) -> &SimpleApplicationEventMultiCaster {
+        &self.event_multi_caster
+    }
+
+    async fn set_parent(
+        self: Arc<Self>,
+        parent: Arc<dyn ConfigurableApplicationContext>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut environment = self.environment.write().await;
+        self.hierarchy
+            .set_parent(self.clone(), parent, &self.bean_factory, &mut environment)
+    }
+
+    fn add_child(&self, child: &Arc<dyn ConfigurableApplicationContext>) {
+        self.hierarchy.add_child(child);
+    }
+
+    fn get_children(&self) -> Vec<Arc<dyn ConfigurableApplicationContext>> {
+        self.hierarchy.get_children()
+    }
+}
 
 lazy_static::lazy_static! {
-    pub static ref APPLICATION_CONTEXT: Arc<RwLock<Box<dyn ConfigurableApplicationContext>>> = {
-        Arc::new(RwLock::new(Box::new(GenericApplicationContext::default())))
+    /// The application context of the running application. It is an `Arc`, not a `Box` as
+    /// it used to be, so that it can be the parent of other contexts.
+    pub static ref APPLICATION_CONTEXT: Arc<RwLock<Arc<dyn ConfigurableApplicationContext>>> = {
+        Arc::new(RwLock::new(Arc::new(GenericApplicationContext::default())))
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::application_event::ApplicationEvenType;
+    use application_beans::factory::disposable_bean::DisposableBean;
+
+    struct Closing {
+        name: &'static str,
+        closed: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    #[async_trait]
+    impl DisposableBean for Closing {
+        async fn destroy(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+            self.closed.lock().unwrap().push(self.name);
+            Ok(())
+        }
+    }
+
+    fn context(
+        name: &'static str,
+        closed: &Arc<Mutex<Vec<&'static str>>>,
+    ) -> Arc<GenericApplicationContext> {
+        let context = Arc::new(GenericApplicationContext::default());
+        let closing = Arc::new(Closing {
+            name,
+            closed: closed.clone(),
+        });
+        context
+            .get_bean_factory()
+            .set_as::<dyn DisposableBean>(name, closing)
+            .unwrap();
+        context
+    }
+
+    /// Records the context events among the destroyed beans.
+    struct Recording(Arc<Mutex<Vec<&'static str>>>);
+
+    #[async_trait]
+    impl ApplicationEventListener for Recording {
+        fn supports_event(&self, _event: &dyn ApplicationEvent) -> bool {
+            true
+        }
+
+        async fn on_event(
+            &self,
+            event: &dyn ApplicationEvent,
+        ) -> Result<(), Box<dyn Error + Send + Sync>> {
+            let name = match event.get_event_type() {
+                ApplicationEvenType::ContextRefreshed => "refreshed",
+                ApplicationEvenType::ContextClosing => "closing",
+                ApplicationEvenType::ContextClosed => "closed",
+                _ => "other",
+            };
+            self.0.lock().unwrap().push(name);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn refresh_publishes_a_context_refreshed_event() {
+        let events = Arc::new(Mutex::new(vec![]));
+        let context = context("bean", &events);
+        context.add_application_listener(Arc::new(Recording(events.clone())));
+
+        context.refresh().await.unwrap();
+
+        assert_eq!(*events.lock().unwrap(), vec!["refreshed"]);
+    }
+
+    #[tokio::test]
+    async fn close_destroys_the_singletons_between_the_closing_and_closed_events() {
+        let events = Arc::new(Mutex::new(vec![]));
+        let context = context("bean", &events);
+        context.add_application_listener(Arc::new(Recording(events.clone())));
+
+        context.close().await.unwrap();
+
+        assert_eq!(*events.lock().unwrap(), vec!["closing", "bean", "closed"]);
+    }
+
+    #[tokio::test]
+    async fn a_child_resolves_beans_from_its_parent_and_is_closed_first() {
+        let closed = Arc::new(Mutex::new(vec![]));
+        let parent = context("parent", &closed);
+        let child = context("child", &closed);
+        parent
+            .get_bean_factory()
+            .set(String::from("shared"))
+            .unwrap();
+        child.clone().set_parent(parent.clone()).await.unwrap();
+
+        assert_eq!(
+            child.get_bean::<String>().as_deref(),
+            Some(&"shared".to_string())
+        );
+        assert_eq!(parent.get_children().len(), 1);
+        assert!(child.clone().set_parent(parent.clone()).await.is_err());
+
+        parent.close().await.unwrap();
+        assert_eq!(*closed.lock().unwrap(), vec!["child", "parent"]);
+    }
+}
@@ -1,7 +1,12 @@
-use std::any::Any;
+use crate::context::application_event_multi_caster::ApplicationEventError;
+use application_beans::factory::bean_definition::BoxFuture;
+use async_trait::async_trait;
+use std::any::{type_name, Any, TypeId};
+use std::error::Error;
+use std::marker::PhantomData;
 use std::sync::Arc;
 
-#[derive(Eq, PartialEq, Debug)]
+#[derive(Eq, PartialEq, Debug, Clone)]
 pub enum ApplicationEvenType {
     Starting,
     EnvironmentPrepared,
@@ -10,15 +15,163 @@ pub enum ApplicationEvenType {
     Started,
     Failed,
     Stopped,
+    ContextRefreshed,
+    ContextClosing,
+    ContextClosed,
+    EnvironmentChanged,
+    /// An event defined by the application, e.g. `Custom("OrderCreated".to_string())`.
+    Custom(String),
 }
 
+#[async_trait]
 pub trait ApplicationEventPublisher {
-    fn publish_event(&self, event: Arc<Box<dyn ApplicationEvent>>);
+    /// Deliver `event` to the listeners supporting it, by order. A failed listener is handled by
+    /// its [`ListenerErrorPolicy`]: `Log` and `Collect` go on with the other listeners, `Collect`
+    /// returning the errors at the end, `Abort` stops the delivery and returns the error.
+    async fn publish_event(
+        &self,
+        event: Arc<dyn ApplicationEvent>,
+    ) -> Result<(), ApplicationEventError>;
+}
+
+/// What happens when a listener fails to handle an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ListenerErrorPolicy {
+    /// Log the error and go on with the other listeners.
+    #[default]
+    Log,
+    /// Stop delivering the event and return the error, which aborts the startup for the
+    /// application events.
+    Abort,
+    /// Go on with the other listeners and return the errors once all of them ran.
+    Collect,
 }
 
 pub trait ApplicationEvent: Send + Sync {
     fn as_any(&self) -> &dyn Any;
-    fn get_event_type(&self) -> ApplicationEvenType;
+    /// Events defined by the application are `Custom` with their type name by default.
+    fn get_event_type(&self) -> ApplicationEvenType {
+        ApplicationEvenType::Custom(type_name::<Self>().to_string())
+    }
+}
+
+/// A listener of the events published by an application context, either added with
+/// `add_application_listener` or registered as a `dyn ApplicationEventListener` bean.
+#[async_trait]
+pub trait ApplicationEventListener: Send + Sync {
+    fn get_name(&self) -> &'static str {
+        type_name::<Self>()
+    }
+
+    /// Listeners with a lower order are called first.
+    fn order(&self) -> i32 {
+        0
+    }
+
+    /// Asynchronous listeners are spawned on the tokio runtime and run concurrently,
+    /// the event is published once all of them completed.
+    fn is_async(&self) -> bool {
+        false
+    }
+
+    fn error_policy(&self) -> ListenerErrorPolicy {
+        ListenerErrorPolicy::Log
+    }
+
+    fn supports_event(&self, event: &dyn ApplicationEvent) -> bool;
+
+    async fn on_event(
+        &self,
+        event: &dyn ApplicationEvent,
+    ) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+/// A listener of the events of type `E`, added to a context with [`EventListenerAdapter`].
+#[async_trait]
+pub trait EventListener<E: ApplicationEvent>: Send + Sync {
+    /// See [`ApplicationEventListener::order`].
+    fn order(&self) -> i32 {
+        0
+    }
+
+    /// See [`ApplicationEventListener::is_async`].
+    fn is_async(&self) -> bool {
+        false
+    }
+
+    fn error_policy(&self) -> ListenerErrorPolicy {
+        ListenerErrorPolicy::Log
+    }
+
+    async fn on_event(&self, event: &E) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+/// The function generated for an `#[event_listener]` function.
+pub type EventListenerFn<E> =
+    for<'a> fn(&'a E) -> BoxFuture<'a, Result<(), Box<dyn Error + Send + Sync>>>;
+
+#[async_trait]
+impl<E: ApplicationEvent + 'static> EventListener<E> for EventListenerFn<E> {
+    async fn on_event(&self, event: &E) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self(event).await
+    }
+}
+
+/// Routes the events of type `E` to an [`EventListener<E>`], e.g.
+/// `add_application_listener(Arc::new(EventListenerAdapter::new(OrderListener {})))`.
+pub struct EventListenerAdapter<E, L> {
+    name: &'static str,
+    listener: L,
+    event: PhantomData<fn(&E)>,
+}
+
+impl<E: ApplicationEvent + 'static, L: EventListener<E>> EventListenerAdapter<E, L> {
+    pub fn new(listener: L) -> Self {
+        Self::named(type_name::<L>(), listener)
+    }
+
+    pub fn named(name: &'static str, listener: L) -> Self {
+        EventListenerAdapter {
+            name,
+            listener,
+            event: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<E: ApplicationEvent + 'static, L: EventListener<E>> ApplicationEventListener
+    for EventListenerAdapter<E, L>
+{
+    fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    fn order(&self) -> i32 {
+        self.listener.order()
+    }
+
+    fn is_async(&self) -> bool {
+        self.listener.is_async()
+    }
+
+    fn error_policy(&self) -> ListenerErrorPolicy {
+        self.listener.error_policy()
+    }
+
+    fn supports_event(&self, event: &dyn ApplicationEvent) -> bool {
+        event.as_any().type_id() == TypeId::of::<E>()
+    }
+
+    async fn on_event(
+        &self,
+        event: &dyn ApplicationEvent,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        match event.as_any().downcast_ref::<E>() {
+            Some(event) => self.listener.on_event(event).await,
+            None => Ok(()),
+        }
+    }
 }
 
 pub struct ApplicationEnvironmentPreparedEvent {}
@@ -64,7 +217,10 @@ impl ApplicationEvent for ApplicationStartedEvent {
     }
 }
 
-pub struct ApplicationFailedEvent {}
+/// Published when the application failed to start, with the reason it failed.
+pub struct ApplicationFailedEvent {
+    pub error: Arc<dyn Error + Send + Sync>,
+}
 
 impl ApplicationEvent for ApplicationFailedEvent {
     fn as_any(&self) -> &dyn Any {
@@ -85,3 +241,150 @@ impl ApplicationEvent for ApplicationStoppedEvent {
         ApplicationEvenType::Stopped
     }
 }
+
+/// Published when the context is refreshed and its lifecycle beans are started.
+pub struct ContextRefreshedEvent {
+    pub context_id: String,
+}
+
+impl ApplicationEvent for ContextRefreshedEvent {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn get_event_type(&self) -> ApplicationEvenType {
+        ApplicationEvenType::ContextRefreshed
+    }
+}
+
+/// Published when the context starts closing, before its beans are stopped and destroyed.
+pub struct ContextClosingEvent {
+    pub context_id: String,
+}
+
+impl ApplicationEvent for ContextClosingEvent {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn get_event_type(&self) -> ApplicationEvenType {
+        ApplicationEvenType::ContextClosing
+    }
+}
+
+/// Published when the beans of the context are destroyed.
+pub struct ContextClosedEvent {
+    pub context_id: String,
+}
+
+impl ApplicationEvent for ContextClosedEvent {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn get_event_type(&self) -> ApplicationEvenType {
+        ApplicationEvenType::ContextClosed
+    }
+}
+
+/// Published when a refresh changed the properties of the environment, once the refresh-scoped
+/// configurations are rebound.
+pub struct EnvironmentChangedEvent {
+    /// The keys added, removed or changed, sorted.
+    pub keys: Vec<String>,
+}
+
+impl ApplicationEvent for EnvironmentChangedEvent {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn get_event_type(&self) -> ApplicationEvenType {
+        ApplicationEvenType::EnvironmentChanged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::application_context::{
+        ConfigurableApplicationContext, GenericApplicationContext,
+    };
+    use std::sync::Mutex;
+
+    struct OrderCreated(u32);
+
+    impl ApplicationEvent for OrderCreated {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    struct OrderCancelled(u32);
+
+    impl ApplicationEvent for OrderCancelled {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    /// Records the orders it receives, all the listeners sharing the same record.
+    struct Recording(&'static str, Arc<Mutex<Vec<String>>>);
+
+    #[async_trait]
+    impl EventListener<OrderCreated> for Recording {
+        async fn on_event(&self, event: &OrderCreated) -> Result<(), Box<dyn Error + Send + Sync>> {
+            self.1
+                .lock()
+                .unwrap()
+                .push(format!("{} {}", self.0, event.0));
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl EventListener<OrderCancelled> for Recording {
+        async fn on_event(
+            &self,
+            event: &OrderCancelled,
+        ) -> Result<(), Box<dyn Error + Send + Sync>> {
+            self.1
+                .lock()
+                .unwrap()
+                .push(format!("{} {}", self.0, event.0));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn an_adapter_supports_only_the_events_of_its_type() {
+        let adapter: EventListenerAdapter<OrderCreated, _> =
+            EventListenerAdapter::new(Recording("created", Default::default()));
+
+        assert!(adapter.supports_event(&OrderCreated(1)));
+        assert!(!adapter.supports_event(&OrderCancelled(1)));
+        assert!(!adapter.supports_event(&ApplicationStartedEvent {}));
+    }
+
+    #[tokio::test]
+    async fn events_reach_the_listeners_of_their_type_in_publish_order() {
+        let record = Arc::new(Mutex::new(vec![]));
+        let context = GenericApplicationContext::default();
+        context.add_application_listener(Arc::new(EventListenerAdapter::<OrderCreated, _>::new(
+            Recording("created", record.clone()),
+        )));
+        context.add_application_listener(Arc::new(EventListenerAdapter::<OrderCancelled, _>::new(
+            Recording("cancelled", record.clone()),
+        )));
+
+        for event in [
+            Arc::new(OrderCreated(1)) as Arc<dyn ApplicationEvent>,
+            Arc::new(OrderCreated(2)),
+            Arc::new(OrderCancelled(1)),
+            Arc::new(OrderCreated(3)),
+        ] {
+            context.publish_event(event).await.unwrap();
+        }
+
+        assert_eq!(
+            *record.lock().unwrap(),
+            vec!["created 1", "created 2", "cancelled 1", "created 3"]
+        );
+    }
+}
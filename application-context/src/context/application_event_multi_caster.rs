@@ -0,0 +1,405 @@
+use crate::context::application_event::{
+    ApplicationEvenType, ApplicationEvent, ApplicationEventListener, ListenerErrorPolicy,
+};
+use application_beans::factory::bean_factory::{BeanFactory, DefaultListableBeanFactory};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use tokio::runtime::{Handle, RuntimeFlavor};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::error;
+
+/// The number of asynchronous listeners running at once by default.
+pub const DEFAULT_CONCURRENCY: usize = 8;
+
+/// The listeners that failed to handle a published event.
+#[derive(Debug)]
+pub struct ApplicationEventError {
+    pub event_type: ApplicationEvenType,
+    /// The name of each failed listener with its error message.
+    pub failures: Vec<(String, String)>,
+}
+
+impl Display for ApplicationEventError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let failures: Vec<String> = self
+            .failures
+            .iter()
+            .map(|(listener, message)| format!("{}: {}", listener, message))
+            .collect();
+        write!(
+            f,
+            "Listeners failed on {:?} event, {}",
+            self.event_type,
+            failures.join("; ")
+        )
+    }
+}
+
+impl Error for ApplicationEventError {}
+
+/// Applies the [`ListenerErrorPolicy`] of the listeners of one event.
+pub struct ListenerErrorCollector {
+    event_type: ApplicationEvenType,
+    failures: Vec<(String, String)>,
+}
+
+impl ListenerErrorCollector {
+    pub fn new(event: &dyn ApplicationEvent) -> Self {
+        ListenerErrorCollector {
+            event_type: event.get_event_type(),
+            failures: vec![],
+        }
+    }
+
+    /// Handle the result of a listener, `Err` if the event must not be delivered any further.
+    pub fn handle(
+        &mut self,
+        listener: &str,
+        policy: ListenerErrorPolicy,
+        result: Result<(), String>,
+    ) -> Result<(), ApplicationEventError> {
+        let Err(message) = result else {
+            return Ok(());
+        };
+        match policy {
+            ListenerErrorPolicy::Log => {
+                error!(
+                    "{} listen on {:?} event failed, {}",
+                    listener, self.event_type, message
+                );
+            }
+            ListenerErrorPolicy::Collect => self.failures.push((listener.to_string(), message)),
+            ListenerErrorPolicy::Abort => {
+                self.failures.push((listener.to_string(), message));
+                return Err(ApplicationEventError {
+                    event_type: self.event_type.clone(),
+                    failures: std::mem::take(&mut self.failures),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// `Err` with the collected failures, if any.
+    pub fn into_result(self) -> Result<(), ApplicationEventError> {
+        if self.failures.is_empty() {
+            Ok(())
+        } else {
+            Err(ApplicationEventError {
+                event_type: self.event_type,
+                failures: self.failures,
+            })
+        }
+    }
+}
+
+/// Delivers the events published by an application context to its listeners by order,
+/// in registration order within the same order.
+pub struct SimpleApplicationEventMultiCaster {
+    listeners: RwLock<Vec<Arc<dyn ApplicationEventListener>>>,
+    concurrency: AtomicUsize,
+}
+
+impl Default for SimpleApplicationEventMultiCaster {
+    fn default() -> Self {
+        SimpleApplicationEventMultiCaster {
+            listeners: Default::default(),
+            concurrency: AtomicUsize::new(DEFAULT_CONCURRENCY),
+        }
+    }
+}
+
+impl SimpleApplicationEventMultiCaster {
+    pub fn add_listener(&self, listener: Arc<dyn ApplicationEventListener>) {
+        self.listeners.write().unwrap().push(listener);
+    }
+
+    /// Set the number of asynchronous listeners running at once for an event.
+    pub fn set_concurrency(&self, concurrency: usize) {
+        self.concurrency
+            .store(concurrency.max(1), Ordering::Relaxed);
+    }
+
+    /// The added listeners and the listener beans of `bean_factory`, by order.
+    pub fn get_listeners(
+        &self,
+        bean_factory: &DefaultListableBeanFactory,
+    ) -> Vec<Arc<dyn ApplicationEventListener>> {
+        let mut listeners = self.listeners.read().unwrap().clone();
+        listeners.extend(bean_factory.get_all::<dyn ApplicationEventListener>());
+        listeners.sort_by_key(|listener| listener.order());
+        listeners
+    }
+
+    pub async fn multicast_event(
+        &self,
+        event: Arc<dyn ApplicationEvent>,
+        bean_factory: &DefaultListableBeanFactory,
+    ) -> Result<(), ApplicationEventError> {
+        // spawned listeners could never run while a current thread runtime is blocked on the event
+        let handle = Handle::try_current()
+            .ok()
+            .filter(|handle| handle.runtime_flavor() == RuntimeFlavor::MultiThread);
+        let semaphore = Arc::new(Semaphore::new(self.concurrency.load(Ordering::Relaxed)));
+        let mut tasks = JoinSet::new();
+        let mut errors = ListenerErrorCollector::new(event.as_ref());
+        for listener in self.get_listeners(bean_factory) {
+            if !listener.supports_event(event.as_ref()) {
+                continue;
+            }
+            match &handle {
+                Some(handle) if listener.is_async() => {
+                    let permit = semaphore.clone().acquire_owned().await.unwrap();
+                    let event = event.clone();
+                    tasks.spawn_on(
+                        async move {
+                            let result = listener.on_event(event.as_ref()).await;
+                            drop(permit);
+                            (listener, result.map_err(|e| e.to_string()))
+                        },
+                        handle,
+                    );
+                }
+                _ => {
+                    let result = listener.on_event(event.as_ref()).await;
+                    errors.handle(
+                        listener.get_name(),
+                        listener.error_policy(),
+                        result.map_err(|e| e.to_string()),
+                    )?;
+                }
+            }
+        }
+        while let Some(task) = tasks.join_next().await {
+            match task {
+                Ok((listener, result)) => {
+                    errors.handle(listener.get_name(), listener.error_policy(), result)?
+                }
+                Err(e) => errors.handle(
+                    "async listener",
+                    ListenerErrorPolicy::Collect,
+                    Err(e.to_string()),
+                )?,
+            }
+        }
+        errors.into_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::any::Any;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    struct TestEvent;
+
+    impl ApplicationEvent for TestEvent {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    struct Listener {
+        name: &'static str,
+        order: i32,
+        policy: ListenerErrorPolicy,
+        fails: bool,
+        calls: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    impl Listener {
+        fn new(name: &'static str, calls: &Arc<Mutex<Vec<&'static str>>>) -> Self {
+            Listener {
+                name,
+                order: 0,
+                policy: ListenerErrorPolicy::Log,
+                fails: false,
+                calls: calls.clone(),
+            }
+        }
+
+        fn order(mut self, order: i32) -> Self {
+            self.order = order;
+            self
+        }
+
+        fn failing(mut self, policy: ListenerErrorPolicy) -> Self {
+            self.policy = policy;
+            self.fails = true;
+            self
+        }
+    }
+
+    #[async_trait]
+    impl ApplicationEventListener for Listener {
+        fn get_name(&self) -> &'static str {
+            self.name
+        }
+
+        fn order(&self) -> i32 {
+            self.order
+        }
+
+        fn error_policy(&self) -> ListenerErrorPolicy {
+            self.policy
+        }
+
+        fn supports_event(&self, _event: &dyn ApplicationEvent) -> bool {
+            true
+        }
+
+        async fn on_event(
+            &self,
+            _event: &dyn ApplicationEvent,
+        ) -> Result<(), Box<dyn Error + Send + Sync>> {
+            self.calls.lock().unwrap().push(self.name);
+            if self.fails {
+                return Err(format!("{} failed", self.name).into());
+            }
+            Ok(())
+        }
+    }
+
+    async fn publish(
+        multi_caster: &SimpleApplicationEventMultiCaster,
+    ) -> Result<(), ApplicationEventError> {
+        multi_caster
+            .multicast_event(Arc::new(TestEvent), &DefaultListableBeanFactory::default())
+            .await
+    }
+
+    #[tokio::test]
+    async fn listeners_are_called_by_order_then_registration() {
+        let calls = Arc::new(Mutex::new(vec![]));
+        let multi_caster = SimpleApplicationEventMultiCaster::default();
+        multi_caster.add_listener(Arc::new(Listener::new("last", &calls).order(10)));
+        multi_caster.add_listener(Arc::new(Listener::new("second", &calls)));
+        multi_caster.add_listener(Arc::new(Listener::new("first", &calls).order(-1)));
+        multi_caster.add_listener(Arc::new(Listener::new("third", &calls)));
+
+        publish(&multi_caster).await.unwrap();
+
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec!["first", "second", "third", "last"]
+        );
+    }
+
+    #[tokio::test]
+    async fn a_logged_failure_does_not_stop_the_delivery() {
+        let calls = Arc::new(Mutex::new(vec![]));
+        let multi_caster = SimpleApplicationEventMultiCaster::default();
+        multi_caster.add_listener(Arc::new(
+            Listener::new("failing", &calls).failing(ListenerErrorPolicy::Log),
+        ));
+        multi_caster.add_listener(Arc::new(Listener::new("next", &calls)));
+
+        publish(&multi_caster).await.unwrap();
+
+        assert_eq!(*calls.lock().unwrap(), vec!["failing", "next"]);
+    }
+
+    #[tokio::test]
+    async fn an_aborting_failure_stops_the_delivery() {
+        let calls = Arc::new(Mutex::new(vec![]));
+        let multi_caster = SimpleApplicationEventMultiCaster::default();
+        multi_caster.add_listener(Arc::new(
+            Listener::new("collected", &calls).failing(ListenerErrorPolicy::Collect),
+        ));
+        multi_caster.add_listener(Arc::new(
+            Listener::new("aborting", &calls).failing(ListenerErrorPolicy::Abort),
+        ));
+        multi_caster.add_listener(Arc::new(Listener::new("skipped", &calls)));
+
+        let error = publish(&multi_caster).await.unwrap_err();
+
+        assert_eq!(*calls.lock().unwrap(), vec!["collected", "aborting"]);
+        assert_eq!(
+            error.failures,
+            vec![
+                ("collected".to_string(), "collected failed".to_string()),
+                ("aborting".to_string(), "aborting failed".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn collected_failures_are_returned_once_every_listener_ran() {
+        let calls = Arc::new(Mutex::new(vec![]));
+        let multi_caster = SimpleApplicationEventMultiCaster::default();
+        multi_caster.add_listener(Arc::new(
+            Listener::new("first", &calls).failing(ListenerErrorPolicy::Collect),
+        ));
+        multi_caster.add_listener(Arc::new(Listener::new("ok", &calls)));
+        multi_caster.add_listener(Arc::new(
+            Listener::new("second", &calls).failing(ListenerErrorPolicy::Collect),
+        ));
+
+        let error = publish(&multi_caster).await.unwrap_err();
+
+        assert_eq!(*calls.lock().unwrap(), vec!["first", "ok", "second"]);
+        assert_eq!(error.event_type, TestEvent.get_event_type());
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Listeners failed on {:?} event, first: first failed; second: second failed",
+                TestEvent.get_event_type()
+            )
+        );
+    }
+
+    /// Counts the asynchronous listeners running at once.
+    #[derive(Default)]
+    struct Running {
+        current: AtomicUsize,
+        max: AtomicUsize,
+        calls: AtomicUsize,
+    }
+
+    struct AsyncListener(Arc<Running>);
+
+    #[async_trait]
+    impl ApplicationEventListener for AsyncListener {
+        fn is_async(&self) -> bool {
+            true
+        }
+
+        fn supports_event(&self, _event: &dyn ApplicationEvent) -> bool {
+            true
+        }
+
+        async fn on_event(
+            &self,
+            _event: &dyn ApplicationEvent,
+        ) -> Result<(), Box<dyn Error + Send + Sync>> {
+            let current = self.0.current.fetch_add(1, Ordering::SeqCst) + 1;
+            self.0.max.fetch_max(current, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.0.current.fetch_sub(1, Ordering::SeqCst);
+            self.0.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn asynchronous_listeners_run_up_to_the_concurrency() {
+        let running = Arc::new(Running::default());
+        let multi_caster = SimpleApplicationEventMultiCaster::default();
+        multi_caster.set_concurrency(2);
+        for _ in 0..6 {
+            multi_caster.add_listener(Arc::new(AsyncListener(running.clone())));
+        }
+
+        publish(&multi_caster).await.unwrap();
+
+        // the event is published once every listener completed
+        assert_eq!(running.calls.load(Ordering::SeqCst), 6);
+        assert_eq!(running.max.load(Ordering::SeqCst), 2);
+    }
+}
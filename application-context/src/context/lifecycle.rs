@@ -0,0 +1,59 @@
+use application_beans::factory::bean_factory::{BeanFactory, DefaultListableBeanFactory};
+use async_trait::async_trait;
+use std::cmp::Reverse;
+use std::error::Error;
+use std::sync::Arc;
+
+/// A bean started when the context is refreshed and stopped when it is closed,
+/// register it as `dyn Lifecycle` to be picked up.
+#[async_trait]
+pub trait Lifecycle: Send + Sync {
+    async fn start(&self) -> Result<(), Box<dyn Error + Send + Sync>>;
+
+    async fn stop(&self) -> Result<(), Box<dyn Error + Send + Sync>>;
+
+    fn is_running(&self) -> bool;
+
+    /// Beans with a lower phase are started first and stopped last.
+    fn get_phase(&self) -> i32 {
+        0
+    }
+}
+
+/// Start the lifecycle beans by phase, in registration order within a phase.
+pub async fn start_lifecycle_beans(
+    bean_factory: &DefaultListableBeanFactory,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut lifecycle_beans = bean_factory.get_all::<dyn Lifecycle>();
+    lifecycle_beans.sort_by_key(|lifecycle_bean| lifecycle_bean.get_phase());
+    for lifecycle_bean in lifecycle_beans {
+        if !lifecycle_bean.is_running() {
+            lifecycle_bean.start().await?;
+        }
+    }
+    Ok(())
+}
+
+/// Stop the running lifecycle beans in the reverse order they were started.
+/// Every bean is stopped even if one fails, the first failure is returned.
+pub async fn stop_lifecycle_beans(
+    bean_factory: &DefaultListableBeanFactory,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut lifecycle_beans: Vec<Arc<dyn Lifecycle>> = bean_factory
+        .get_all::<dyn Lifecycle>()
+        .into_iter()
+        .rev()
+        .collect();
+    lifecycle_beans.sort_by_key(|lifecycle_bean| Reverse(lifecycle_bean.get_phase()));
+    let mut result = Ok(());
+    for lifecycle_bean in lifecycle_beans {
+        if lifecycle_bean.is_running() {
+            if let Err(e) = lifecycle_bean.stop().await {
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+    }
+    result
+}
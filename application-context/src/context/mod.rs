@@ -1,2 +1,4 @@
 pub mod application_context;
 pub mod application_event;
+pub mod application_event_multi_caster;
+pub mod lifecycle;
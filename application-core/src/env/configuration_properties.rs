@@ -0,0 +1,541 @@
+use crate::env::environment::ApplicationEnvironment;
+use crate::env::origin::PropertyOrigin;
+use crate::env::property_resolver::PropertyResolver;
+use config::Value;
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use std::any::type_name;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// Typed configuration bound from the properties under `PREFIX`, e.g. `application.cloud`.
+///
+/// Usually implemented with `#[derive(ConfigurationProperties)]`, which also validates the
+/// fields and registers the bound configuration as a bean.
+pub trait ConfigurationProperties: Sized + Send + Sync + 'static {
+    const PREFIX: &'static str;
+
+    /// Bind the configuration from `environment`, reporting every invalid property at once.
+    fn bind(environment: &ApplicationEnvironment) -> Result<Self, BindError>;
+}
+
+/// A property that is missing, can not be converted or fails a validation rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidProperty {
+    pub key: String,
+    pub value: Option<String>,
+    pub origin: Option<PropertyOrigin>,
+    pub reason: String,
+}
+
+impl Display for InvalidProperty {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.key)?;
+        if let Some(value) = &self.value {
+            write!(f, " = \"{}\"", value)?;
+        }
+        if let Some(origin) = &self.origin {
+            write!(f, " (from {})", origin)?;
+        }
+        write!(f, ": {}", self.reason)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindError {
+    pub invalid_properties: Vec<InvalidProperty>,
+}
+
+impl Display for BindError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to bind the configuration properties")?;
+        for invalid_property in &self.invalid_properties {
+            write!(f, "\n    {}", invalid_property)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for BindError {}
+
+/// Binds the fields of a configuration one by one, collecting the invalid properties.
+///
+/// A field is looked up under its relaxed names, `smtp_host` is also read from `smtp-host`,
+/// `smtpHost` and the `APP_MAIL_SMTP_HOST` environment variable for the prefix `app.mail`.
+pub struct PropertyBinder<'a> {
+    environment: &'a ApplicationEnvironment,
+    prefix: &'a str,
+    /// The key each bound field was found under.
+    keys: HashMap<String, String>,
+    invalid_fields: HashSet<String>,
+    invalid_properties: Vec<InvalidProperty>,
+}
+
+impl<'a> PropertyBinder<'a> {
+    pub fn new(environment: &'a ApplicationEnvironment, prefix: &'a str) -> Self {
+        PropertyBinder {
+            environment,
+            prefix,
+            keys: HashMap::new(),
+            invalid_fields: HashSet::new(),
+            invalid_properties: vec![],
+        }
+    }
+
+    /// The value of the field `name`, `None` if it is not set or can not be converted.
+    pub fn bind<T: DeserializeOwned>(&mut self, name: &str) -> Option<T> {
+        let key = self.find_key(name)?;
+        let value = self.environment.get_property::<T>(&key);
+        if value.is_none() {
+            self.reject(
+                name,
+                format!("can not be converted to {}", type_name::<T>()),
+            );
+        }
+        value
+    }
+
+    /// The duration of the field `name`, e.g. `30s`, milliseconds without unit.
+    pub fn bind_duration(&mut self, name: &str) -> Option<Duration> {
+        let text = self.bind::<String>(name)?;
+        match parse_duration(&text) {
+            Ok(duration) => Some(duration),
+            Err(reason) => {
+                self.reject(name, reason);
+                None
+            }
+        }
+    }
+
+    /// Report the field `name` as missing if it has no `value` and was not reported already.
+    pub fn require<T>(&mut self, name: &str, value: Option<T>) -> Option<T> {
+        if value.is_none() && !self.invalid_fields.contains(name) {
+            self.reject(name, "is required but not set".to_string());
+        }
+        value
+    }
+
+    /// Report the field `name` if it fails a validation rule.
+    pub fn validate(&mut self, name: &str, result: Result<(), String>) {
+        if let Err(reason) = result {
+            self.reject(name, reason);
+        }
+    }
+
+    pub fn finish(self) -> Result<(), BindError> {
+        if self.invalid_properties.is_empty() {
+            return Ok(());
+        }
+        Err(BindError {
+            invalid_properties: self.invalid_properties,
+        })
+    }
+
+    fn find_key(&mut self, name: &str) -> Option<String> {
+        let key = relaxed_keys(self.prefix, name)
+            .into_iter()
+            .find(|key| self.environment.get_property::<Value>(key).is_some())?;
+        self.keys.insert(name.to_string(), key.clone());
+        Some(key)
+    }
+
+    fn reject(&mut self, name: &str, reason: String) {
+        let (key, value, origin) = match self.keys.get(name) {
+            Some(key) => (
+                key.clone(),
+                self.environment
+                    .get_property::<Value>(key)
+                    .map(|value| value.to_string()),
+                self.environment.get_property_origin(key),
+            ),
+            None => (join_key(self.prefix, name), None, None),
+        };
+        self.invalid_fields.insert(name.to_string());
+        self.invalid_properties.push(InvalidProperty {
+            key,
+            value,
+            origin,
+            reason,
+        });
+    }
+}
+
+fn join_key(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", prefix, name)
+    }
+}
+
+/// The keys of the field `name` by preference: snake, kebab and camel case, then the key
+/// an environment variable is read as.
+fn relaxed_keys(prefix: &str, name: &str) -> Vec<String> {
+    let camel_case = name
+        .split('_')
+        .enumerate()
+        .map(|(index, word)| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) if index > 0 => first.to_uppercase().chain(chars).collect(),
+                _ => word.to_string(),
+            }
+        })
+        .collect::<String>();
+    let mut keys = vec![
+        join_key(prefix, name),
+        join_key(prefix, &name.replace('_', "-")),
+        join_key(prefix, &camel_case),
+        join_key(prefix, name)
+            .to_lowercase()
+            .replace(['_', '-'], "."),
+    ];
+    let mut seen = HashSet::new();
+    keys.retain(|key| seen.insert(key.clone()));
+    keys
+}
+
+/// Parse a duration such as `500ms`, `30s`, `5m`, `1h` or `1d`, milliseconds without unit.
+pub fn parse_duration(text: &str) -> Result<Duration, String> {
+    let text = text.trim();
+    let unit_index = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let (amount, unit) = text.split_at(unit_index);
+    let invalid = || format!("'{}' is not a duration, e.g. 500ms, 30s, 5m or 1h", text);
+    let amount = amount.parse::<u64>().map_err(|_| invalid())?;
+    let seconds = |factor: u64| amount.checked_mul(factor).map(Duration::from_secs);
+    let duration = match unit.trim() {
+        "ns" => Some(Duration::from_nanos(amount)),
+        "us" | "µs" => Some(Duration::from_micros(amount)),
+        "" | "ms" => Some(Duration::from_millis(amount)),
+        "s" => Some(Duration::from_secs(amount)),
+        "m" => seconds(60),
+        "h" => seconds(60 * 60),
+        "d" => seconds(60 * 60 * 24),
+        _ => None,
+    };
+    duration.ok_or_else(invalid)
+}
+
+/// Validation rules of `#[validate(...)]`, an error is the reason the value is invalid.
+pub mod rules {
+    use super::*;
+
+    /// `#[validate(range(min = 1, max = 65535))]`, both bounds are optional.
+    pub fn range<T: PartialOrd + Display>(
+        value: &T,
+        min: Option<T>,
+        max: Option<T>,
+    ) -> Result<(), String> {
+        match (min, max) {
+            (Some(min), Some(max)) if *value < min || *value > max => {
+                Err(format!("must be between {} and {}", min, max))
+            }
+            (Some(min), None) if *value < min => Err(format!("must be at least {}", min)),
+            (None, Some(max)) if *value > max => Err(format!("must be at most {}", max)),
+            _ => Ok(()),
+        }
+    }
+
+    /// `#[validate(non_empty)]`
+    pub fn non_empty<T: IsEmpty + ?Sized>(value: &T) -> Result<(), String> {
+        if value.is_empty() {
+            return Err("must not be empty".to_string());
+        }
+        Ok(())
+    }
+
+    /// `#[validate(regex = "^[a-z]+$")]`
+    pub fn regex<T: AsRef<str> + ?Sized>(value: &T, pattern: &str) -> Result<(), String> {
+        let regex = Regex::new(pattern)
+            .map_err(|e| format!("has an invalid pattern {}, {}", pattern, e))?;
+        if !regex.is_match(value.as_ref()) {
+            return Err(format!("must match \"{}\"", pattern));
+        }
+        Ok(())
+    }
+
+    /// `#[validate(duration(min = "1s", max = "1h"))]` on a `Duration` or a text field,
+    /// both bounds are optional.
+    pub fn duration<T: ToDuration + ?Sized>(
+        value: &T,
+        min: Option<&str>,
+        max: Option<&str>,
+    ) -> Result<(), String> {
+        let value = value.to_duration()?;
+        let min = min.map(parse_duration).transpose()?;
+        let max = max.map(parse_duration).transpose()?;
+        match (min, max) {
+            (Some(min), Some(max)) if value < min || value > max => {
+                Err(format!("must be between {:?} and {:?}", min, max))
+            }
+            (Some(min), None) if value < min => Err(format!("must be at least {:?}", min)),
+            (None, Some(max)) if value > max => Err(format!("must be at most {:?}", max)),
+            _ => Ok(()),
+        }
+    }
+
+    pub trait IsEmpty {
+        fn is_empty(&self) -> bool;
+    }
+
+    impl IsEmpty for str {
+        fn is_empty(&self) -> bool {
+            str::is_empty(self)
+        }
+    }
+
+    impl IsEmpty for String {
+        fn is_empty(&self) -> bool {
+            String::is_empty(self)
+        }
+    }
+
+    impl<T> IsEmpty for Vec<T> {
+        fn is_empty(&self) -> bool {
+            Vec::is_empty(self)
+        }
+    }
+
+    impl<K, V> IsEmpty for HashMap<K, V> {
+        fn is_empty(&self) -> bool {
+            HashMap::is_empty(self)
+        }
+    }
+
+    impl<K, V> IsEmpty for BTreeMap<K, V> {
+        fn is_empty(&self) -> bool {
+            BTreeMap::is_empty(self)
+        }
+    }
+
+    pub trait ToDuration {
+        fn to_duration(&self) -> Result<Duration, String>;
+    }
+
+    impl ToDuration for Duration {
+        fn to_duration(&self) -> Result<Duration, String> {
+            Ok(*self)
+        }
+    }
+
+    impl ToDuration for str {
+        fn to_duration(&self) -> Result<Duration, String> {
+            parse_duration(self)
+        }
+    }
+
+    impl ToDuration for String {
+        fn to_duration(&self) -> Result<Duration, String> {
+            parse_duration(self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::env::environment::ConfigurableEnvironment;
+    use crate::env::property::PropertySource;
+    use config::Config;
+
+    fn environment(properties: &[(&str, &str)]) -> ApplicationEnvironment {
+        let mut builder = Config::builder();
+        for (key, value) in properties {
+            builder = builder.set_override(*key, *value).unwrap();
+        }
+        let mut environment = ApplicationEnvironment::default();
+        environment.add_property_source(PropertySource {
+            name: "test".to_string(),
+            source: builder.build().unwrap(),
+        });
+        environment
+    }
+
+    #[test]
+    fn fields_are_found_under_their_relaxed_keys() {
+        for key in [
+            "app.mail.smtp_host",
+            "app.mail.smtp-host",
+            "app.mail.smtpHost",
+            "app.mail.smtp.host",
+        ] {
+            let environment = environment(&[(key, "localhost")]);
+            let mut binder = PropertyBinder::new(&environment, "app.mail");
+            assert_eq!(
+                binder.bind::<String>("smtp_host"),
+                Some("localhost".to_string()),
+                "{}",
+                key
+            );
+            assert!(binder.finish().is_ok());
+        }
+    }
+
+    #[test]
+    fn the_snake_case_key_is_preferred() {
+        let environment = environment(&[
+            ("app.mail.smtp-host", "kebab"),
+            ("app.mail.smtp_host", "snake"),
+        ]);
+        let mut binder = PropertyBinder::new(&environment, "app.mail");
+        assert_eq!(
+            binder.bind::<String>("smtp_host"),
+            Some("snake".to_string())
+        );
+        assert_eq!(
+            relaxed_keys("", "smtp_host"),
+            vec!["smtp_host", "smtp-host", "smtpHost", "smtp.host"]
+        );
+    }
+
+    #[test]
+    fn missing_fields_are_reported_only_if_required() {
+        let environment = environment(&[]);
+        let mut binder = PropertyBinder::new(&environment, "app.mail");
+        let username = binder.bind::<String>("username");
+        assert_eq!(username, None);
+        // a field with a default is not required
+        let port = binder.bind::<u16>("port").or(Some(25));
+        assert_eq!(binder.require("port", port), Some(25));
+        assert!(binder.finish().is_ok());
+
+        let mut binder = PropertyBinder::new(&environment, "app.mail");
+        let host = binder.bind::<String>("host");
+        assert_eq!(binder.require("host", host), None);
+        let error = binder.finish().unwrap_err();
+        assert_eq!(
+            error.invalid_properties,
+            vec![InvalidProperty {
+                key: "app.mail.host".to_string(),
+                value: None,
+                origin: None,
+                reason: "is required but not set".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn every_invalid_field_is_reported() {
+        let environment = environment(&[
+            ("app.mail.port", "not a port"),
+            ("app.mail.timeout", "5 minutes"),
+            ("app.mail.smtp-host", ""),
+        ]);
+        let mut binder = PropertyBinder::new(&environment, "app.mail");
+        let port = binder.bind::<u16>("port");
+        // a value that can not be converted is not reported as missing too
+        binder.require("port", port);
+        binder.bind_duration("timeout");
+        let smtp_host = binder.bind::<String>("smtp_host").unwrap();
+        binder.validate("smtp_host", rules::non_empty(&smtp_host));
+
+        let error = binder.finish().unwrap_err();
+        let reported: Vec<(&str, Option<&str>)> = error
+            .invalid_properties
+            .iter()
+            .map(|property| (property.key.as_str(), property.value.as_deref()))
+            .collect();
+        assert_eq!(
+            reported,
+            vec![
+                ("app.mail.port", Some("not a port")),
+                ("app.mail.timeout", Some("5 minutes")),
+                ("app.mail.smtp-host", Some("")),
+            ]
+        );
+        assert_eq!(
+            error.invalid_properties[0].reason,
+            "can not be converted to u16"
+        );
+        assert_eq!(error.invalid_properties[2].reason, "must not be empty");
+        assert!(error.to_string().starts_with(
+            "Failed to bind the configuration properties\n    app.mail.port = \"not a port\""
+        ));
+    }
+
+    #[test]
+    fn durations_are_parsed_with_their_unit() {
+        assert_eq!(parse_duration("250"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration(" 30s "), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_duration("5m"), Ok(Duration::from_secs(300)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(3600)));
+        assert_eq!(parse_duration("2d"), Ok(Duration::from_secs(172800)));
+        assert_eq!(parse_duration("10us"), Ok(Duration::from_micros(10)));
+        for text in ["", "s", "-1s", "1.5s", "10y", "99999999999999999d"] {
+            assert_eq!(
+                parse_duration(text),
+                Err(format!(
+                    "'{}' is not a duration, e.g. 500ms, 30s, 5m or 1h",
+                    text
+                )),
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn range_rule() {
+        assert!(rules::range(&80, Some(1), Some(65535)).is_ok());
+        assert_eq!(
+            rules::range(&0, Some(1), Some(65535)),
+            Err("must be between 1 and 65535".to_string())
+        );
+        assert_eq!(
+            rules::range(&0.5, Some(1.0), None),
+            Err("must be at least 1".to_string())
+        );
+        assert_eq!(
+            rules::range(&11, None, Some(10)),
+            Err("must be at most 10".to_string())
+        );
+        assert!(rules::range(&11, None, None).is_ok());
+    }
+
+    #[test]
+    fn non_empty_rule() {
+        assert!(rules::non_empty("a").is_ok());
+        assert!(rules::non_empty(&vec![1]).is_ok());
+        assert!(rules::non_empty("").is_err());
+        assert!(rules::non_empty(&Vec::<u8>::new()).is_err());
+        assert!(rules::non_empty(&HashMap::<String, String>::new()).is_err());
+        assert!(rules::non_empty(&BTreeMap::<String, String>::new()).is_err());
+    }
+
+    #[test]
+    fn regex_rule() {
+        assert!(rules::regex("mail", "^[a-z]+$").is_ok());
+        assert_eq!(
+            rules::regex("Mail", "^[a-z]+$"),
+            Err("must match \"^[a-z]+$\"".to_string())
+        );
+        assert!(rules::regex("mail", "(")
+            .unwrap_err()
+            .starts_with("has an invalid pattern ("));
+    }
+
+    #[test]
+    fn duration_rule() {
+        let timeout = Duration::from_secs(30);
+        assert!(rules::duration(&timeout, Some("1s"), Some("1m")).is_ok());
+        assert!(rules::duration("30s", Some("1s"), Some("1m")).is_ok());
+        assert_eq!(
+            rules::duration(&timeout, Some("1m"), Some("1h")),
+            Err("must be between 60s and 3600s".to_string())
+        );
+        assert_eq!(
+            rules::duration("2h", None, Some("1h")),
+            Err("must be at most 3600s".to_string())
+        );
+        assert_eq!(
+            rules::duration("10ms", Some("1s"), None),
+            Err("must be at least 1s".to_string())
+        );
+        assert!(rules::duration("soon", None, None).is_err());
+    }
+}
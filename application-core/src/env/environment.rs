@@ -1,8 +1,14 @@
+use crate::env::origin::{OriginTrackedValue, PropertyOrigin};
+use crate::env::placeholder::{PlaceholderError, PlaceholderResolver, PLACEHOLDER_PREFIX};
 use crate::env::property::{MutablePropertySources, PropertySource};
 use crate::env::property_resolver::PropertyResolver;
+use async_std::task::block_on;
 use async_trait::async_trait;
+use config::{Value, ValueKind};
 use serde::Deserialize;
-use tokio::sync::{RwLockReadGuard, RwLockWriteGuard};
+use std::sync::Arc;
+use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use tracing::warn;
 
 pub trait Environment: PropertyResolver {
     fn get_active_profiles(&self) -> Vec<String>;
@@ -14,6 +20,11 @@ pub trait ConfigurableEnvironment: Environment {
     fn add_property_source(&mut self, property_source: PropertySource);
 
     fn get_property_sources(&self) -> &MutablePropertySources;
+
+    fn get_property_sources_mut(&mut self) -> &mut MutablePropertySources;
+
+    /// Resolve the properties missing from this environment from `parent`.
+    fn set_parent(&mut self, parent: Arc<RwLock<ApplicationEnvironment>>);
 }
 #[derive(Default, Clone)]
 pub struct ApplicationEnvironment {
@@ -21,6 +32,7 @@ pub struct ApplicationEnvironment {
     config_locations: Option<Vec<String>>,
     config_file_names: Option<Vec<String>>,
     property_sources: MutablePropertySources,
+    parent: Option<Arc<RwLock<ApplicationEnvironment>>>,
 }
 
 impl ApplicationEnvironment {
@@ -34,8 +46,71 @@ impl ApplicationEnvironment {
             config_locations: locations,
             config_file_names: file_names,
             property_sources: Default::default(),
+            parent: None,
+        }
+    }
+
+    /// The origin of the value of `key` in the first property source that has it, the parent's
+    /// included.
+    pub fn get_property_origin(&self, key: &str) -> Option<PropertyOrigin> {
+        self.get_raw_values(key)
+            .into_iter()
+            .next()
+            .map(|(_, origin)| origin)
+    }
+
+    /// The value of `key` in the first property source that has it, placeholders unresolved.
+    fn get_raw_property(&self, key: &str) -> Option<String> {
+        for property_source in self.get_property_sources().get_sources() {
+            if let Ok(value) = property_source.get_property::<String>(key) {
+                return Some(value);
+            }
+        }
+        let parent = self.parent.as_ref()?;
+        block_on(parent.read()).get_raw_property(key)
+    }
+
+    /// The values of `key` in the property sources that have it with their origin, by precedence.
+    fn get_raw_values(&self, key: &str) -> Vec<(Value, PropertyOrigin)> {
+        let mut values: Vec<(Value, PropertyOrigin)> = self
+            .get_property_sources()
+            .get_sources()
+            .iter()
+            .filter_map(|property_source| {
+                let value = property_source.get_value(key)?;
+                let origin = PropertyOrigin::new(&property_source.name, &value);
+                Some((value, origin))
+            })
+            .collect();
+        if let Some(parent) = self.parent.as_ref() {
+            values.extend(block_on(parent.read()).get_raw_values(key));
+        }
+        values
+    }
+}
+
+/// Resolve the placeholders of the strings of `value`, tables and arrays included.
+fn resolve_value(
+    value: &mut Value,
+    resolver: &PlaceholderResolver,
+) -> Result<(), PlaceholderError> {
+    match &mut value.kind {
+        ValueKind::String(text) if text.contains(PLACEHOLDER_PREFIX) => {
+            *text = resolver.resolve(text)?;
+        }
+        ValueKind::Table(table) => {
+            for value in table.values_mut() {
+                resolve_value(value, resolver)?;
+            }
         }
+        ValueKind::Array(array) => {
+            for value in array.iter_mut() {
+                resolve_value(value, resolver)?;
+            }
+        }
+        _ => {}
     }
+    Ok(())
 }
 
 impl Environment for ApplicationEnvironment {
@@ -60,18 +135,41 @@ impl ConfigurableEnvironment for ApplicationEnvironment {
     fn get_property_sources(&self) -> &MutablePropertySources {
         &self.property_sources
     }
+
+    fn get_property_sources_mut(&mut self) -> &mut MutablePropertySources {
+        &mut self.property_sources
+    }
+
+    fn set_parent(&mut self, parent: Arc<RwLock<ApplicationEnvironment>>) {
+        self.parent = Some(parent);
+    }
 }
 
 impl PropertyResolver for ApplicationEnvironment {
-    fn get_property<'de, T: Deserialize<'de>>(&self, key: &str) -> Option<T> {
-        for property_source in self.get_property_sources().get_sources() {
-            let result = property_source.get_property::<T>(key);
-            if result.is_ok() {
-                return result.ok();
+    fn get_property_with_origin<'de, T: Deserialize<'de>>(
+        &self,
+        key: &str,
+    ) -> Option<OriginTrackedValue<T>> {
+        let lookup = |key: &str| self.get_raw_property(key);
+        // unresolvable placeholders are kept as they are, e.g. in values meant for another tool
+        let resolver = PlaceholderResolver::new(&lookup, false);
+        for (mut value, origin) in self.get_raw_values(key) {
+            // circular placeholders make the property unresolvable
+            if let Err(e) = resolve_value(&mut value, &resolver) {
+                warn!("Property '{}' can not be resolved, {}", key, e);
+                return None;
+            }
+            if let Ok(value) = value.try_deserialize::<T>() {
+                return Some(OriginTrackedValue { value, origin });
             }
         }
         None
     }
+
+    fn resolve_placeholders(&self, text: &str) -> Result<String, PlaceholderError> {
+        let lookup = |key: &str| self.get_raw_property(key);
+        PlaceholderResolver::new(&lookup, true).resolve(text)
+    }
 }
 
 #[async_trait]
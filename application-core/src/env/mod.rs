@@ -1,3 +1,6 @@
+pub mod configuration_properties;
 pub mod environment;
+pub mod origin;
+pub mod placeholder;
 pub mod property;
 pub mod property_resolver;
@@ -0,0 +1,66 @@
+use config::Value;
+use std::fmt::{Display, Formatter};
+
+/// Where the value of a property comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyOrigin {
+    /// The name of the property source, e.g. `configProperties`.
+    pub source: String,
+    /// What the property source read the value from, e.g. a file path, an environment
+    /// variable or a Consul key, `None` for values set in code.
+    pub location: Option<String>,
+    pub line: Option<usize>,
+}
+
+impl PropertyOrigin {
+    /// The origin of `value` read by the property source named `source`, whose location is
+    /// set by the source of the configuration with [`origin_location`].
+    pub fn new(source: &str, value: &Value) -> Self {
+        let (location, line) = match value.origin() {
+            Some(origin) => match origin.rsplit_once(LINE_SEPARATOR) {
+                Some((location, line)) if !location.is_empty() => match line.parse::<usize>() {
+                    Ok(line) => (Some(location.to_string()), Some(line)),
+                    Err(_) => (Some(origin.to_string()), None),
+                },
+                _ => (Some(origin.to_string()), None),
+            },
+            None => (None, None),
+        };
+        PropertyOrigin {
+            source: source.to_string(),
+            location,
+            line,
+        }
+    }
+}
+
+impl Display for PropertyOrigin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)?;
+        match (&self.location, self.line) {
+            (Some(location), Some(line)) => write!(f, " ({}:{})", location, line),
+            (Some(location), None) => write!(f, " ({})", location),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The value of a property with its origin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OriginTrackedValue<T> {
+    pub value: T,
+    pub origin: PropertyOrigin,
+}
+
+/// Separates the line from the location in the origin of a value, `:` would be ambiguous with
+/// addresses such as `http://localhost:8500`.
+const LINE_SEPARATOR: &str = "#L";
+
+/// The origin a source of configuration sets on the values it reads, `location#Lline` if the
+/// line is known.
+pub fn origin_location(location: &str, line: Option<usize>) -> String {
+    match line {
+        Some(line) => format!("{}{}{}", location, LINE_SEPARATOR, line),
+        None => location.to_string(),
+    }
+}
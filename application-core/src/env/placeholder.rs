@@ -0,0 +1,282 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+pub const PLACEHOLDER_PREFIX: &str = "${";
+pub const PLACEHOLDER_SUFFIX: char = '}';
+/// Separates the key of a placeholder from its default value, `${key:default}`.
+pub const VALUE_SEPARATOR: char = ':';
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlaceholderError {
+    /// The key of the placeholder has no value and the placeholder no default.
+    Unresolvable { key: String },
+    /// The values of the keys reference each other, the cycle starts and ends with the same key.
+    Circular { cycle: Vec<String> },
+}
+
+impl Display for PlaceholderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlaceholderError::Unresolvable { key } => {
+                write!(f, "Could not resolve placeholder '{}'", key)
+            }
+            PlaceholderError::Circular { cycle } => {
+                write!(f, "Circular placeholder reference {}", cycle.join(" -> "))
+            }
+        }
+    }
+}
+
+impl Error for PlaceholderError {}
+
+/// Replaces the `${key}` and `${key:default}` placeholders of a text with the values of `lookup`.
+///
+/// Keys, defaults and values may contain placeholders themselves. The unresolvable
+/// placeholders are an error if `strict`, kept as they are otherwise.
+pub struct PlaceholderResolver<'a> {
+    lookup: &'a dyn Fn(&str) -> Option<String>,
+    strict: bool,
+}
+
+impl<'a> PlaceholderResolver<'a> {
+    pub fn new(lookup: &'a dyn Fn(&str) -> Option<String>, strict: bool) -> Self {
+        PlaceholderResolver { lookup, strict }
+    }
+
+    pub fn resolve(&self, text: &str) -> Result<String, PlaceholderError> {
+        self.resolve_with(text, &mut vec![])
+    }
+
+    /// `visiting` holds the keys whose values are being resolved.
+    fn resolve_with(
+        &self,
+        text: &str,
+        visiting: &mut Vec<String>,
+    ) -> Result<String, PlaceholderError> {
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find(PLACEHOLDER_PREFIX) {
+            result.push_str(&rest[..start]);
+            let inner = &rest[start + PLACEHOLDER_PREFIX.len()..];
+            let Some(end) = find_placeholder_end(inner) else {
+                // not a placeholder without its suffix
+                rest = &rest[start..];
+                break;
+            };
+            let placeholder = &inner[..end];
+            result.push_str(&self.resolve_placeholder(placeholder, visiting)?);
+            rest = &inner[end + PLACEHOLDER_SUFFIX.len_utf8()..];
+        }
+        result.push_str(rest);
+        Ok(result)
+    }
+
+    fn resolve_placeholder(
+        &self,
+        placeholder: &str,
+        visiting: &mut Vec<String>,
+    ) -> Result<String, PlaceholderError> {
+        let (key, default) = match find_separator(placeholder) {
+            Some(index) => (
+                &placeholder[..index],
+                Some(&placeholder[index + VALUE_SEPARATOR.len_utf8()..]),
+            ),
+            None => (placeholder, None),
+        };
+        let key = self.resolve_with(key, visiting)?;
+        if let Some(position) = visiting.iter().position(|visited| *visited == key) {
+            let mut cycle = visiting[position..].to_vec();
+            cycle.push(key);
+            return Err(PlaceholderError::Circular { cycle });
+        }
+        match (self.lookup)(&key) {
+            Some(value) => {
+                visiting.push(key);
+                let value = self.resolve_with(&value, visiting);
+                visiting.pop();
+                value
+            }
+            None => match default {
+                Some(default) => self.resolve_with(default, visiting),
+                None if self.strict => Err(PlaceholderError::Unresolvable { key }),
+                None => Ok(format!(
+                    "{}{}{}",
+                    PLACEHOLDER_PREFIX, placeholder, PLACEHOLDER_SUFFIX
+                )),
+            },
+        }
+    }
+}
+
+/// The index of the suffix matching the prefix just before `text`, skipping nested placeholders.
+fn find_placeholder_end(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut index = 0;
+    while index < text.len() {
+        if text[index..].starts_with(PLACEHOLDER_PREFIX) {
+            depth += 1;
+            index += PLACEHOLDER_PREFIX.len();
+            continue;
+        }
+        let c = text[index..].chars().next()?;
+        if c == PLACEHOLDER_SUFFIX {
+            if depth == 0 {
+                return Some(index);
+            }
+            depth -= 1;
+        }
+        index += c.len_utf8();
+    }
+    None
+}
+
+/// The index of the first separator outside of nested placeholders.
+fn find_separator(placeholder: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut index = 0;
+    while index < placeholder.len() {
+        if placeholder[index..].starts_with(PLACEHOLDER_PREFIX) {
+            depth += 1;
+            index += PLACEHOLDER_PREFIX.len();
+            continue;
+        }
+        let c = placeholder[index..].chars().next()?;
+        match c {
+            PLACEHOLDER_SUFFIX => depth -= 1,
+            VALUE_SEPARATOR if depth == 0 => return Some(index),
+            _ => {}
+        }
+        index += c.len_utf8();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn resolve(
+        properties: &[(&str, &str)],
+        text: &str,
+        strict: bool,
+    ) -> Result<String, PlaceholderError> {
+        let properties: HashMap<String, String> = properties
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        let lookup = |key: &str| properties.get(key).cloned();
+        PlaceholderResolver::new(&lookup, strict).resolve(text)
+    }
+
+    #[test]
+    fn placeholders_are_replaced_by_their_values() {
+        let properties = [("host", "localhost"), ("port", "8080")];
+
+        assert_eq!(
+            resolve(&properties, "http://${host}:${port}/", true),
+            Ok("http://localhost:8080/".to_string())
+        );
+        assert_eq!(
+            resolve(&properties, "no placeholder", true),
+            Ok("no placeholder".to_string())
+        );
+    }
+
+    #[test]
+    fn nested_placeholders_are_resolved() {
+        let properties = [
+            ("profile", "dev"),
+            ("url.dev", "http://${host}"),
+            ("host", "localhost"),
+        ];
+
+        // in the key, in the value and in the default
+        assert_eq!(
+            resolve(&properties, "${url.${profile}}", true),
+            Ok("http://localhost".to_string())
+        );
+        assert_eq!(
+            resolve(&properties, "${missing:${host}}", true),
+            Ok("localhost".to_string())
+        );
+        assert_eq!(
+            resolve(&properties, "${missing:${other:${host}}}", true),
+            Ok("localhost".to_string())
+        );
+    }
+
+    #[test]
+    fn defaults_may_contain_the_separator() {
+        assert_eq!(
+            resolve(&[], "${url:http://localhost:8080}", true),
+            Ok("http://localhost:8080".to_string())
+        );
+        assert_eq!(resolve(&[], "${empty:}", true), Ok("".to_string()));
+        assert_eq!(
+            resolve(
+                &[("url", "http://example.com")],
+                "${url:http://localhost:8080}",
+                true
+            ),
+            Ok("http://example.com".to_string())
+        );
+    }
+
+    #[test]
+    fn unresolvable_placeholders_fail_only_if_strict() {
+        assert_eq!(
+            resolve(&[], "${missing}", true),
+            Err(PlaceholderError::Unresolvable {
+                key: "missing".to_string()
+            })
+        );
+        assert_eq!(
+            resolve(&[], "a ${missing} b", false),
+            Ok("a ${missing} b".to_string())
+        );
+    }
+
+    #[test]
+    fn unterminated_placeholders_are_kept() {
+        let properties = [("host", "localhost")];
+
+        assert_eq!(
+            resolve(&properties, "${host", true),
+            Ok("${host".to_string())
+        );
+        assert_eq!(
+            resolve(&properties, "${host} ${port", true),
+            Ok("localhost ${port".to_string())
+        );
+        assert_eq!(
+            resolve(&properties, "${a:${host}", true),
+            Ok("${a:${host}".to_string())
+        );
+    }
+
+    #[test]
+    fn cycles_are_named_from_the_first_repeated_key() {
+        let properties = [("a", "${b}"), ("b", "${c}"), ("c", "${b}")];
+
+        assert_eq!(
+            resolve(&properties, "${a}", false),
+            Err(PlaceholderError::Circular {
+                cycle: vec!["b".to_string(), "c".to_string(), "b".to_string()]
+            })
+        );
+        assert_eq!(
+            resolve(&[("a", "x${a}")], "${a}", false)
+                .unwrap_err()
+                .to_string(),
+            "Circular placeholder reference a -> a"
+        );
+    }
+
+    #[test]
+    fn a_key_used_twice_is_not_a_cycle() {
+        let properties = [("a", "${b}-${b}"), ("b", "x")];
+
+        assert_eq!(resolve(&properties, "${a}", true), Ok("x-x".to_string()));
+    }
+}
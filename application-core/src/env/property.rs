@@ -1,6 +1,8 @@
-use config::Config;
+use config::{Config, Source, Value};
 use serde::Deserialize;
 use std::collections::LinkedList;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
 
 /// PropertySource representing a source of name/value property pairs.
 #[derive(Default, Clone)]
@@ -18,19 +20,276 @@ impl PropertySource {
     ) -> Result<T, config::ConfigError> {
         self.source.get::<T>(key)
     }
+
+    /// The value of `key` with the origin set by the source of the configuration.
+    pub fn get_value(&self, key: &str) -> Option<Value> {
+        // deserializing a value drops its origin, the tables keep it
+        let value = match key.rsplit_once('.') {
+            Some((parent, last)) => self
+                .source
+                .get_table(parent)
+                .ok()
+                .and_then(|mut table| table.remove(last)),
+            None => self
+                .source
+                .collect()
+                .ok()
+                .and_then(|mut table| table.remove(key)),
+        };
+        value.or_else(|| self.source.get::<Value>(key).ok())
+    }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertySourceNotFoundError {
+    pub name: String,
+}
+
+impl Display for PropertySourceNotFoundError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Property source '{}' does not exist", self.name)
+    }
+}
+
+impl Error for PropertySourceNotFoundError {}
+
+/// The property sources of an environment by precedence, the first one that has a property wins.
+///
+/// The names are unique, adding a source replaces the source of the same name wherever it was.
 #[derive(Default, Clone)]
 pub struct MutablePropertySources {
     sources: LinkedList<PropertySource>,
 }
 
 impl MutablePropertySources {
+    /// Add the source with the highest precedence.
+    pub fn add_first(&mut self, property_source: PropertySource) {
+        self.remove(&property_source.name);
+        self.sources.push_front(property_source);
+    }
+
+    /// Add the source with the lowest precedence.
     pub fn add_last(&mut self, property_source: PropertySource) {
+        self.remove(&property_source.name);
         self.sources.push_back(property_source);
     }
 
+    /// Add the source with the precedence just above the source named `relative`.
+    pub fn add_before(
+        &mut self,
+        relative: &str,
+        property_source: PropertySource,
+    ) -> Result<(), PropertySourceNotFoundError> {
+        self.insert(relative, property_source, 0)
+    }
+
+    /// Add the source with the precedence just below the source named `relative`.
+    pub fn add_after(
+        &mut self,
+        relative: &str,
+        property_source: PropertySource,
+    ) -> Result<(), PropertySourceNotFoundError> {
+        self.insert(relative, property_source, 1)
+    }
+
+    /// Replace the source named `name` by `property_source`, keeping its precedence. Nothing
+    /// changes if there is no source named `name`.
+    pub fn replace(
+        &mut self,
+        name: &str,
+        property_source: PropertySource,
+    ) -> Result<PropertySource, PropertySourceNotFoundError> {
+        if !self.contains(name) {
+            return Err(PropertySourceNotFoundError {
+                name: name.to_string(),
+            });
+        }
+        if property_source.name != name {
+            self.remove(&property_source.name);
+        }
+        let source = self
+            .sources
+            .iter_mut()
+            .find(|source| source.name == name)
+            .ok_or_else(|| PropertySourceNotFoundError {
+                name: name.to_string(),
+            })?;
+        Ok(std::mem::replace(source, property_source))
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<PropertySource> {
+        let index = self.precedence_of(name)?;
+        let mut tail = self.sources.split_off(index);
+        let removed = tail.pop_front();
+        self.sources.append(&mut tail);
+        removed
+    }
+
+    pub fn get(&self, name: &str) -> Option<&PropertySource> {
+        self.sources.iter().find(|source| source.name == name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// The position of the source named `name`, 0 for the highest precedence.
+    pub fn precedence_of(&self, name: &str) -> Option<usize> {
+        self.sources.iter().position(|source| source.name == name)
+    }
+
     pub fn get_sources(&self) -> &LinkedList<PropertySource> {
         &self.sources
     }
+
+    /// The names of the sources by precedence.
+    pub fn get_names(&self) -> Vec<&str> {
+        self.sources
+            .iter()
+            .map(|source| source.name.as_str())
+            .collect()
+    }
+
+    fn insert(
+        &mut self,
+        relative: &str,
+        property_source: PropertySource,
+        offset: usize,
+    ) -> Result<(), PropertySourceNotFoundError> {
+        if property_source.name == relative {
+            return self.replace(relative, property_source).map(|_| ());
+        }
+        if !self.contains(relative) {
+            return Err(PropertySourceNotFoundError {
+                name: relative.to_string(),
+            });
+        }
+        self.remove(&property_source.name);
+        let index = self.precedence_of(relative).unwrap_or_default() + offset;
+        let mut tail = self.sources.split_off(index);
+        self.sources.push_back(property_source);
+        self.sources.append(&mut tail);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(name: &str, value: &str) -> PropertySource {
+        PropertySource {
+            name: name.to_string(),
+            source: Config::builder()
+                .set_override("key", value)
+                .and_then(|builder| builder.build())
+                .unwrap(),
+        }
+    }
+
+    fn sources(names: &[&str]) -> MutablePropertySources {
+        let mut sources = MutablePropertySources::default();
+        for name in names {
+            sources.add_last(source(name, name));
+        }
+        sources
+    }
+
+    #[test]
+    fn add_first_and_last() {
+        let mut sources = sources(&["b"]);
+        sources.add_first(source("a", "a"));
+        sources.add_last(source("c", "c"));
+        assert_eq!(sources.get_names(), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn adding_an_existing_name_moves_it() {
+        let mut sources = sources(&["a", "b", "c"]);
+        sources.add_first(source("c", "new"));
+        assert_eq!(sources.get_names(), vec!["c", "a", "b"]);
+        assert_eq!(
+            sources
+                .get("c")
+                .unwrap()
+                .get_property::<String>("key")
+                .unwrap(),
+            "new"
+        );
+    }
+
+    #[test]
+    fn add_before_and_after() {
+        let mut sources = sources(&["a", "c"]);
+        sources.add_before("c", source("b", "b")).unwrap();
+        sources.add_after("c", source("d", "d")).unwrap();
+        sources.add_before("a", source("first", "first")).unwrap();
+        assert_eq!(sources.get_names(), vec!["first", "a", "b", "c", "d"]);
+
+        sources.add_after("first", source("d", "d")).unwrap();
+        assert_eq!(sources.get_names(), vec!["first", "d", "a", "b", "c"]);
+    }
+
+    #[test]
+    fn add_relative_to_a_missing_source() {
+        let mut sources = sources(&["a"]);
+        let error = sources.add_before("missing", source("b", "b")).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Property source 'missing' does not exist"
+        );
+        assert!(sources.add_after("missing", source("b", "b")).is_err());
+        assert_eq!(sources.get_names(), vec!["a"]);
+    }
+
+    #[test]
+    fn replace_keeps_the_precedence() {
+        let mut sources = sources(&["a", "b", "c"]);
+        let replaced = sources.replace("b", source("b", "new")).unwrap();
+        assert_eq!(replaced.get_property::<String>("key").unwrap(), "b");
+        assert_eq!(sources.get_names(), vec!["a", "b", "c"]);
+        assert_eq!(
+            sources
+                .get("b")
+                .unwrap()
+                .get_property::<String>("key")
+                .unwrap(),
+            "new"
+        );
+
+        sources.replace("a", source("c", "c")).unwrap();
+        assert_eq!(sources.get_names(), vec!["c", "b"]);
+        assert!(sources.replace("missing", source("d", "d")).is_err());
+    }
+
+    #[test]
+    fn replace_a_missing_source_keeps_the_others() {
+        let mut sources = sources(&["a", "b"]);
+        let Err(error) = sources.replace("missing", source("a", "new")) else {
+            panic!("a missing source was replaced");
+        };
+        assert_eq!(
+            error.to_string(),
+            "Property source 'missing' does not exist"
+        );
+        assert_eq!(sources.get_names(), vec!["a", "b"]);
+        assert_eq!(
+            sources
+                .get("a")
+                .unwrap()
+                .get_property::<String>("key")
+                .unwrap(),
+            "a"
+        );
+    }
+
+    #[test]
+    fn remove() {
+        let mut sources = sources(&["a", "b", "c"]);
+        assert_eq!(sources.remove("b").unwrap().name, "b");
+        assert!(sources.remove("b").is_none());
+        assert_eq!(sources.get_names(), vec!["a", "c"]);
+        assert_eq!(sources.precedence_of("c"), Some(1));
+        assert!(!sources.contains("b"));
+    }
 }
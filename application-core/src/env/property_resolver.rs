@@ -1,10 +1,27 @@
+use crate::env::origin::OriginTrackedValue;
+use crate::env::placeholder::PlaceholderError;
 use serde::Deserialize;
 
 pub trait PropertyResolver {
-    fn get_property<'de, T: Deserialize<'de>>(&self, key: &str) -> Option<T>;
+    /// The property of `key`, with the `${key:default}` placeholders of its values resolved.
+    fn get_property<'de, T: Deserialize<'de>>(&self, key: &str) -> Option<T> {
+        self.get_property_with_origin::<T>(key)
+            .map(|property| property.value)
+    }
+
+    /// The property of `key` like [`PropertyResolver::get_property`], with the property source,
+    /// file, line or environment variable it comes from.
+    fn get_property_with_origin<'de, T: Deserialize<'de>>(
+        &self,
+        key: &str,
+    ) -> Option<OriginTrackedValue<T>>;
 
     fn get_property_default<'de, T: Deserialize<'de>>(&self, key: &str, data: T) -> T {
         let property = self.get_property::<T>(key);
         property.unwrap_or(data)
     }
+
+    /// Replace the `${key}` and `${key:default}` placeholders of `text` with the properties,
+    /// failing on a placeholder without property nor default.
+    fn resolve_placeholders(&self, text: &str) -> Result<String, PlaceholderError>;
 }
@@ -1,7 +1,10 @@
 use crate::scheduling::Task;
-use application_beans::factory::bean_factory::BeanFactory;
+use application_beans::factory::bean_error::BeanError;
+use application_beans::factory::bean_factory::{BeanFactory, ConfigurableBeanFactory};
+use application_beans::factory::disposable_bean::DisposableBean;
 use application_context::context::application_context::APPLICATION_CONTEXT;
 use application_core::lang::runnable::Runnable;
+use async_trait::async_trait;
 use bimap::BiMap;
 use std::collections::HashMap;
 use std::error::Error;
@@ -21,6 +24,8 @@ pub struct Scheduler {
 }
 
 impl Scheduler {
+    pub const BEAN_NAME: &'static str = "scheduler";
+
     pub async fn new() -> Result<Scheduler, Box<dyn Error>> {
         let mut scheduler = JobScheduler::new().await?;
 
@@ -42,6 +47,17 @@ impl Scheduler {
         })
     }
 
+    /// Register the scheduler as a bean that is shut down with the context.
+    pub fn register(
+        self,
+        bean_factory: &impl ConfigurableBeanFactory,
+    ) -> Result<Arc<Scheduler>, BeanError> {
+        let scheduler = Arc::new(self);
+        bean_factory.set_as(Self::BEAN_NAME, scheduler.clone())?;
+        bean_factory.set_as::<dyn DisposableBean>(Self::BEAN_NAME, scheduler.clone())?;
+        Ok(scheduler)
+    }
+
     pub async fn start(&self) -> Result<(), Box<dyn Error>> {
         self.internal.start().await?;
         Ok(())
@@ -161,3 +177,12 @@ impl Scheduler {
         job_ids
     }
 }
+
+#[async_trait]
+impl DisposableBean for Scheduler {
+    async fn destroy(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.shutdown().await.map_err(|e| e.to_string())?;
+        self.internal.clone().shutdown().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,252 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, ToTokens};
+use syn::{Data, DeriveInput, Fields, GenericArgument, Ident, LitStr, PathArguments, Type};
+
+struct ComponentField {
+    ident: Ident,
+    value: TokenStream2,
+}
+
+pub(crate) struct Component {
+    ident: Ident,
+    name: String,
+    lazy: bool,
+    scope: TokenStream2,
+    depends_on: Vec<LitStr>,
+    /// The types of the beans resolved by type.
+    type_dependencies: Vec<Type>,
+    fields: Vec<ComponentField>,
+}
+
+impl Component {
+    fn new(ast: DeriveInput) -> syn::Result<Self> {
+        if !ast.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &ast.generics,
+                "#[derive(Component)] does not support generic types",
+            ));
+        }
+        let ident = ast.ident;
+        let mut name = lower_camel_case(&ident.to_string());
+        let mut lazy = false;
+        let mut scope = quote!(Singleton);
+        for attr in ast
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("component"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    name = meta.value()?.parse::<LitStr>()?.value();
+                } else if meta.path.is_ident("lazy") {
+                    lazy = true;
+                } else if meta.path.is_ident("scope") {
+                    let value = meta.value()?.parse::<LitStr>()?;
+                    scope = match value.value().as_str() {
+                        "singleton" => quote!(Singleton),
+                        "prototype" => quote!(Prototype),
+                        "request" => quote!(Request),
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                value,
+                                r#"scope must be "singleton", "prototype" or "request""#,
+                            ))
+                        }
+                    };
+                } else {
+                    return Err(meta.error("unsupported component attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        let fields = match ast.data {
+            Data::Struct(data) => match data.fields {
+                Fields::Named(fields) => fields.named.into_iter().collect(),
+                Fields::Unit => vec![],
+                Fields::Unnamed(fields) => {
+                    return Err(syn::Error::new_spanned(
+                        fields,
+                        "#[derive(Component)] requires named fields",
+                    ))
+                }
+            },
+            _ => {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "#[derive(Component)] is only supported on structs",
+                ))
+            }
+        };
+
+        let mut depends_on = vec![];
+        let mut type_dependencies = vec![];
+        let mut component_fields = vec![];
+        for field in fields {
+            let ident = field.ident.unwrap();
+            let ty = field.ty;
+            let mut value = None;
+            for attr in &field.attrs {
+                if attr.path().is_ident("value") {
+                    let key = attr.parse_args::<LitStr>()?;
+                    value = Some(quote! {
+                        ::application_beans::factory::component::resolve_value::<#ty>(bean_factory, #key).await?
+                    });
+                } else if attr.path().is_ident("bean") {
+                    let bean_name = attr.parse_args::<LitStr>()?;
+                    value = Some(match (generic_argument(&ty, "Arc"), optional_bean(&ty)) {
+                        (Some(inner), _) => quote! {
+                            ::application_beans::factory::component::resolve_named_bean::<#inner>(bean_factory, #bean_name)?
+                        },
+                        (None, Some(inner)) => quote! {
+                            ::application_beans::factory::bean_factory::BeanFactory::get_named::<#inner>(bean_factory, #bean_name)
+                        },
+                        (None, None) => return Err(field_type_error(&ty)),
+                    });
+                    depends_on.push(bean_name);
+                }
+            }
+            let value = match value {
+                Some(value) => value,
+                None => match (generic_argument(&ty, "Arc"), optional_bean(&ty)) {
+                    (Some(inner), _) => {
+                        type_dependencies.push(inner.clone());
+                        quote! {
+                            ::application_beans::factory::component::resolve_bean::<#inner>(bean_factory)?
+                        }
+                    }
+                    (None, Some(inner)) => {
+                        type_dependencies.push(inner.clone());
+                        quote! {
+                            ::application_beans::factory::bean_factory::BeanFactory::get_bean::<#inner>(bean_factory)
+                        }
+                    }
+                    (None, None) => return Err(field_type_error(&ty)),
+                },
+            };
+            component_fields.push(ComponentField { ident, value });
+        }
+
+        Ok(Self {
+            ident,
+            name,
+            lazy,
+            scope,
+            depends_on,
+            type_dependencies,
+            fields: component_fields,
+        })
+    }
+}
+
+impl ToTokens for Component {
+    fn to_tokens(&self, output: &mut TokenStream2) {
+        let Self {
+            ident,
+            name,
+            lazy,
+            scope,
+            depends_on,
+            type_dependencies,
+            fields,
+        } = self;
+        let field_idents = fields.iter().map(|field| &field.ident);
+        let field_values = fields.iter().map(|field| &field.value);
+        let lazy = lazy.then(|| quote!(.lazy()));
+
+        let stream = quote! {
+            const _: () = {
+                #[allow(non_camel_case_types)]
+                struct __component_factory;
+
+                fn __create_component(
+                    bean_factory: &::application_beans::factory::bean_factory::DefaultListableBeanFactory,
+                ) -> ::application_beans::factory::bean_definition::BoxFuture<
+                    '_,
+                    ::application_beans::factory::bean_definition::BeanCreationResult<#ident>,
+                > {
+                    ::std::boxed::Box::pin(async move {
+                        Ok(#ident {
+                            #(#field_idents: #field_values,)*
+                        })
+                    })
+                }
+
+                impl ::application_beans::factory::component::ComponentFactory for __component_factory {
+                    fn register(
+                        &self,
+                        bean_factory: &::application_beans::factory::bean_factory::DefaultListableBeanFactory,
+                    ) -> ::std::result::Result<(), ::application_beans::factory::bean_error::BeanError> {
+                        let definition = ::application_beans::factory::bean_definition::BeanDefinition::new_async(
+                            #name,
+                            __create_component,
+                        )
+                        #(.depends_on(#depends_on))*
+                        #(.depends_on_type::<#type_dependencies>())*
+                        #lazy
+                        .scope(::application_beans::factory::scope::Scope::#scope);
+                        ::application_beans::factory::bean_factory::ConfigurableBeanFactory::register_bean_definition(
+                            bean_factory,
+                            definition,
+                        )
+                    }
+                }
+
+                ::application_beans::submit_component!(__component_factory);
+            };
+        };
+
+        output.extend(stream);
+    }
+}
+
+pub(crate) fn with_component(input: TokenStream) -> TokenStream {
+    let ast = match syn::parse::<DeriveInput>(input) {
+        Ok(ast) => ast,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    match Component::new(ast) {
+        Ok(component) => component.into_token_stream().into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// `T` of `Option<Arc<T>>`
+fn optional_bean(ty: &Type) -> Option<&Type> {
+    generic_argument(ty, "Option").and_then(|ty| generic_argument(ty, "Arc"))
+}
+
+/// The type argument of `wrapper<T>`
+pub(crate) fn generic_argument<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != wrapper {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first()? {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    }
+}
+
+fn field_type_error(ty: &Type) -> syn::Error {
+    syn::Error::new_spanned(
+        ty,
+        r#"component fields must be `Arc<T>` or `Option<Arc<T>>`, resolved by type or by name with `#[bean("name")]`, or have a `#[value("key")]` attribute"#,
+    )
+}
+
+pub(crate) fn lower_camel_case(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,343 @@
+use crate::component::{generic_argument, lower_camel_case};
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::meta::ParseNestedMeta;
+use syn::{Data, DeriveInput, Expr, Fields, Ident, LitStr, Type};
+
+enum Rule {
+    Range(Option<TokenStream2>, Option<TokenStream2>),
+    NonEmpty,
+    Regex(LitStr),
+    Duration(Option<LitStr>, Option<LitStr>),
+}
+
+impl Rule {
+    fn parse(meta: &ParseNestedMeta) -> syn::Result<Self> {
+        if meta.path.is_ident("range") {
+            let (mut min, mut max) = (None, None);
+            meta.parse_nested_meta(|bound| {
+                if bound.path.is_ident("min") {
+                    min = Some(bound.value()?.parse::<Expr>()?.into_token_stream());
+                } else if bound.path.is_ident("max") {
+                    max = Some(bound.value()?.parse::<Expr>()?.into_token_stream());
+                } else {
+                    return Err(bound.error("range supports min and max"));
+                }
+                Ok(())
+            })?;
+            Ok(Rule::Range(min, max))
+        } else if meta.path.is_ident("non_empty") {
+            Ok(Rule::NonEmpty)
+        } else if meta.path.is_ident("regex") {
+            Ok(Rule::Regex(meta.value()?.parse::<LitStr>()?))
+        } else if meta.path.is_ident("duration") {
+            let (mut min, mut max) = (None, None);
+            if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|bound| {
+                    if bound.path.is_ident("min") {
+                        min = Some(bound.value()?.parse::<LitStr>()?);
+                    } else if bound.path.is_ident("max") {
+                        max = Some(bound.value()?.parse::<LitStr>()?);
+                    } else {
+                        return Err(bound.error("duration supports min and max"));
+                    }
+                    Ok(())
+                })?;
+            }
+            Ok(Rule::Duration(min, max))
+        } else {
+            Err(meta.error("unsupported validation rule, use range, non_empty, regex or duration"))
+        }
+    }
+
+    fn to_check(&self) -> TokenStream2 {
+        let rules = quote!(::application_core::env::configuration_properties::rules);
+        match self {
+            Rule::Range(min, max) => {
+                let min = option_tokens(min.as_ref());
+                let max = option_tokens(max.as_ref());
+                quote!(#rules::range(value, #min, #max))
+            }
+            Rule::NonEmpty => quote!(#rules::non_empty(value)),
+            Rule::Regex(pattern) => quote!(#rules::regex(value, #pattern)),
+            Rule::Duration(min, max) => {
+                let min = option_tokens(min.as_ref());
+                let max = option_tokens(max.as_ref());
+                quote!(#rules::duration(value, #min, #max))
+            }
+        }
+    }
+}
+
+struct PropertiesField {
+    ident: Ident,
+    /// The type bound from the properties, `T` of an `Option<T>` field.
+    ty: Type,
+    optional: bool,
+    default: Option<TokenStream2>,
+    rules: Vec<Rule>,
+}
+
+pub(crate) struct ConfigurationProperties {
+    ident: Ident,
+    prefix: LitStr,
+    name: String,
+    refresh: bool,
+    fields: Vec<PropertiesField>,
+}
+
+impl ConfigurationProperties {
+    fn new(ast: DeriveInput) -> syn::Result<Self> {
+        if !ast.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &ast.generics,
+                "#[derive(ConfigurationProperties)] does not support generic types",
+            ));
+        }
+        let ident = ast.ident;
+        let mut prefix = None;
+        let mut name = lower_camel_case(&ident.to_string());
+        let mut refresh = false;
+        for attr in ast
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("config"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("prefix") {
+                    prefix = Some(meta.value()?.parse::<LitStr>()?);
+                } else if meta.path.is_ident("name") {
+                    name = meta.value()?.parse::<LitStr>()?.value();
+                } else if meta.path.is_ident("refresh") {
+                    refresh = true;
+                } else {
+                    return Err(meta.error("unsupported config attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        let prefix = prefix.ok_or_else(|| {
+            syn::Error::new_spanned(
+                &ident,
+                r#"#[derive(ConfigurationProperties)] requires #[config(prefix = "...")]"#,
+            )
+        })?;
+
+        let fields = match ast.data {
+            Data::Struct(data) => match data.fields {
+                Fields::Named(fields) => fields.named.into_iter().collect::<Vec<_>>(),
+                _ => {
+                    return Err(syn::Error::new(
+                        Span::call_site(),
+                        "#[derive(ConfigurationProperties)] requires named fields",
+                    ))
+                }
+            },
+            _ => {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "#[derive(ConfigurationProperties)] is only supported on structs",
+                ))
+            }
+        };
+
+        let mut properties_fields = vec![];
+        for field in fields {
+            let ident = field.ident.unwrap();
+            let (ty, optional) = match generic_argument(&field.ty, "Option") {
+                Some(inner) => (inner.clone(), true),
+                None => (field.ty.clone(), false),
+            };
+            let mut default = None;
+            let mut rules = vec![];
+            for attr in &field.attrs {
+                if attr.path().is_ident("config") {
+                    attr.parse_nested_meta(|meta| {
+                        if !meta.path.is_ident("default") {
+                            return Err(meta.error("unsupported config attribute"));
+                        }
+                        default = Some(if meta.input.peek(syn::Token![=]) {
+                            meta.value()?.parse::<Expr>()?.into_token_stream()
+                        } else {
+                            quote!(::std::default::Default::default())
+                        });
+                        Ok(())
+                    })?;
+                } else if attr.path().is_ident("validate") {
+                    attr.parse_nested_meta(|meta| {
+                        rules.push(Rule::parse(&meta)?);
+                        Ok(())
+                    })?;
+                }
+            }
+            properties_fields.push(PropertiesField {
+                ident,
+                ty,
+                optional,
+                default,
+                rules,
+            });
+        }
+
+        Ok(Self {
+            ident,
+            prefix,
+            name,
+            refresh,
+            fields: properties_fields,
+        })
+    }
+}
+
+impl ToTokens for ConfigurationProperties {
+    fn to_tokens(&self, output: &mut TokenStream2) {
+        let Self {
+            ident,
+            prefix,
+            name,
+            refresh,
+            fields,
+        } = self;
+        let bindings = fields.iter().map(|field| {
+            let PropertiesField {
+                ident,
+                ty,
+                optional,
+                default,
+                rules,
+            } = field;
+            let key = ident.unraw().to_string();
+            let local = local_ident(ident);
+            let bind = if is_duration(ty) {
+                quote!(__binder.bind_duration(#key))
+            } else {
+                quote!(__binder.bind::<#ty>(#key))
+            };
+            let default = default
+                .as_ref()
+                .map(|default| quote!(let #local = #local.or_else(|| Some(#default));));
+            let require = (!optional).then(|| quote!(let #local = __binder.require(#key, #local);));
+            let checks = rules.iter().map(Rule::to_check);
+            let validate = (!rules.is_empty()).then(|| {
+                quote! {
+                    if let Some(value) = &#local {
+                        #(__binder.validate(#key, #checks);)*
+                    }
+                }
+            });
+            quote! {
+                let #local = #bind;
+                #default
+                #require
+                #validate
+            }
+        });
+        let field_values = fields.iter().map(|field| {
+            let ident = &field.ident;
+            let local = local_ident(ident);
+            if field.optional {
+                quote!(#ident: #local)
+            } else {
+                // set once the binder has no invalid property
+                quote!(#ident: #local.unwrap())
+            }
+        });
+
+        let register = if *refresh {
+            quote!(register_refreshable_configuration_properties)
+        } else {
+            quote!(register_configuration_properties)
+        };
+        let is_refresh_scoped = refresh.then(|| {
+            quote! {
+                fn is_refresh_scoped(&self) -> bool {
+                    true
+                }
+            }
+        });
+
+        let stream = quote! {
+            impl ::application_core::env::configuration_properties::ConfigurationProperties for #ident {
+                const PREFIX: &'static str = #prefix;
+
+                fn bind(
+                    environment: &::application_core::env::environment::ApplicationEnvironment,
+                ) -> ::std::result::Result<Self, ::application_core::env::configuration_properties::BindError> {
+                    let mut __binder = ::application_core::env::configuration_properties::PropertyBinder::new(
+                        environment,
+                        Self::PREFIX,
+                    );
+                    #(#bindings)*
+                    __binder.finish()?;
+                    Ok(#ident {
+                        #(#field_values,)*
+                    })
+                }
+            }
+
+            const _: () = {
+                #[allow(non_camel_case_types)]
+                struct __configuration_properties_factory;
+
+                impl ::application_beans::factory::configuration_properties::ConfigurationPropertiesFactory
+                    for __configuration_properties_factory
+                {
+                    fn bind(
+                        &self,
+                        environment: &::application_core::env::environment::ApplicationEnvironment,
+                        bean_factory: &::application_beans::factory::bean_factory::DefaultListableBeanFactory,
+                    ) -> ::std::result::Result<(), ::std::boxed::Box<dyn ::std::error::Error + Send + Sync>> {
+                        ::application_beans::factory::configuration_properties::#register::<#ident>(
+                            #name,
+                            environment,
+                            bean_factory,
+                        )
+                    }
+
+                    #is_refresh_scoped
+                }
+
+                ::application_beans::submit_configuration_properties!(__configuration_properties_factory);
+            };
+        };
+
+        output.extend(stream);
+    }
+}
+
+pub(crate) fn with_configuration_properties(input: TokenStream) -> TokenStream {
+    let ast = match syn::parse::<DeriveInput>(input) {
+        Ok(ast) => ast,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    match ConfigurationProperties::new(ast) {
+        Ok(properties) => properties.into_token_stream().into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn is_duration(ty: &Type) -> bool {
+    let Type::Path(type_path) = ty else {
+        return false;
+    };
+    type_path
+        .path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "Duration" && segment.arguments.is_empty())
+}
+
+/// The local variable of a field, apart from the variables of the generated code.
+fn local_ident(ident: &Ident) -> Ident {
+    format_ident!("__{}", ident.unraw())
+}
+
+fn option_tokens<T: ToTokens>(value: Option<&T>) -> TokenStream2 {
+    match value {
+        Some(value) => quote!(::std::option::Option::Some(#value)),
+        None => quote!(::std::option::Option::None),
+    }
+}
@@ -0,0 +1,110 @@
+use crate::input_and_compile_error;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::{FnArg, ItemFn, LitStr, Type};
+
+pub(crate) struct EventListener {
+    name: String,
+    event: Type,
+    ast: ItemFn,
+}
+
+impl EventListener {
+    fn new(args: TokenStream, ast: ItemFn) -> syn::Result<Self> {
+        let mut name = ast.sig.ident.to_string();
+        if !args.is_empty() {
+            let parser = syn::meta::parser(|meta| {
+                if meta.path.is_ident("name") {
+                    name = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported event_listener attribute"))
+                }
+            });
+            syn::parse::Parser::parse(parser, args)?;
+        }
+        if ast.sig.asyncness.is_none() {
+            return Err(syn::Error::new_spanned(
+                ast.sig.fn_token,
+                "#[event_listener] requires an async function",
+            ));
+        }
+        let event = match ast.sig.inputs.first() {
+            Some(FnArg::Typed(arg)) if ast.sig.inputs.len() == 1 => match arg.ty.as_ref() {
+                Type::Reference(reference) if reference.mutability.is_none() => {
+                    reference.elem.as_ref().clone()
+                }
+                _ => return Err(event_argument_error(&ast)),
+            },
+            _ => return Err(event_argument_error(&ast)),
+        };
+        Ok(Self { name, event, ast })
+    }
+}
+
+impl ToTokens for EventListener {
+    fn to_tokens(&self, output: &mut TokenStream2) {
+        let Self { name, event, ast } = self;
+        let ident = &ast.sig.ident;
+
+        let stream = quote! {
+            #ast
+
+            const _: () = {
+                #[allow(non_camel_case_types)]
+                struct __event_listener_factory;
+
+                fn __on_event(
+                    event: &#event,
+                ) -> ::application_beans::factory::bean_definition::BoxFuture<
+                    '_,
+                    ::std::result::Result<
+                        (),
+                        ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>,
+                    >,
+                > {
+                    ::std::boxed::Box::pin(#ident(event))
+                }
+
+                impl ::application_beans::factory::component::ComponentFactory for __event_listener_factory {
+                    fn register(
+                        &self,
+                        bean_factory: &::application_beans::factory::bean_factory::DefaultListableBeanFactory,
+                    ) -> ::std::result::Result<(), ::application_beans::factory::bean_error::BeanError> {
+                        let listener = ::application_context::context::application_event::EventListenerAdapter::named(
+                            ::std::concat!(::std::module_path!(), "::", ::std::stringify!(#ident)),
+                            __on_event as ::application_context::context::application_event::EventListenerFn<#event>,
+                        );
+                        ::application_beans::factory::bean_factory::ConfigurableBeanFactory::set_as::<
+                            dyn ::application_context::context::application_event::ApplicationEventListener,
+                        >(bean_factory, #name, ::std::sync::Arc::new(listener))
+                    }
+                }
+
+                ::application_beans::submit_component!(__event_listener_factory);
+            };
+        };
+
+        output.extend(stream);
+    }
+}
+
+pub(crate) fn with_event_listener(args: TokenStream, input: TokenStream) -> TokenStream {
+    let ast = match syn::parse::<ItemFn>(input.clone()) {
+        Ok(ast) => ast,
+        Err(err) => return input_and_compile_error(input, err),
+    };
+
+    match EventListener::new(args, ast) {
+        Ok(event_listener) => event_listener.into_token_stream().into(),
+        Err(err) => input_and_compile_error(input, err),
+    }
+}
+
+fn event_argument_error(ast: &ItemFn) -> syn::Error {
+    syn::Error::new_spanned(
+        &ast.sig.inputs,
+        "#[event_listener] functions take the event by reference, e.g. `event: &OrderCreated`",
+    )
+}
@@ -1,3 +1,6 @@
+mod component;
+mod configuration_properties;
+mod event_listener;
 mod nest;
 mod route;
 
@@ -125,6 +128,87 @@ pub fn nest(args: TokenStream, input: TokenStream) -> TokenStream {
     nest::with_nest(args, input)
 }
 
+/// Registers the struct as a bean, created when the application context is refreshed.
+///
+/// # Attributes
+/// - `#[component(name = "userService", lazy, scope = "prototype")]`: optional, the bean is
+///   named after the struct in lower camel case by default.
+/// - `Arc<T>` and `Option<Arc<T>>` fields are resolved from the bean of type `T`, created first.
+/// - `#[bean("name")]`: resolves the field from the bean registered under `name`.
+/// - `#[value("key")]`: resolves the field from the property `key` of the environment.
+///
+/// # Examples
+/// ```ignore
+/// # use application_web_macros::Component;
+/// #[derive(Component)]
+/// struct UserService {
+///     dao: Arc<Dao>,
+///     #[value("application.name")]
+///     application_name: String,
+/// }
+/// ```
+#[proc_macro_derive(Component, attributes(component, bean, value))]
+pub fn component(input: TokenStream) -> TokenStream {
+    component::with_component(input)
+}
+
+/// Binds the struct from the properties under a prefix, validates it and registers it as a bean
+/// before the application context is refreshed. The startup fails with every invalid property.
+///
+/// # Attributes
+/// - `#[config(prefix = "app.mail", name = "mailProperties")]`: the prefix is required, the bean
+///   is named after the struct in lower camel case by default.
+/// - `#[config(refresh)]`: the bean is a `Refreshable<T>` bound again when the environment is
+///   refreshed, an invalid property keeps the previous instance.
+/// - Fields are read from their snake case, kebab case, camel case or environment variable key,
+///   e.g. `smtp_host`, `smtp-host`, `smtpHost` or `APP_MAIL_SMTP_HOST`. `Option` fields are
+///   optional, `Duration` fields are read from texts such as `30s`.
+/// - `#[config(default)]` or `#[config(default = expr)]`: the value of a field without property.
+/// - `#[validate(range(min = 1, max = 65535), non_empty, regex = "^[a-z]+$",
+///   duration(min = "1s", max = "1h"))]`: the rules the value of a field must pass.
+///
+/// # Examples
+/// ```ignore
+/// # use application_web_macros::ConfigurationProperties;
+/// #[derive(ConfigurationProperties)]
+/// #[config(prefix = "app.mail")]
+/// struct MailProperties {
+///     #[validate(non_empty)]
+///     smtp_host: String,
+///     #[config(default = 25)]
+///     #[validate(range(min = 1, max = 65535))]
+///     port: u16,
+///     #[config(default = Duration::from_secs(30))]
+///     #[validate(duration(max = "5m"))]
+///     timeout: Duration,
+///     username: Option<String>,
+/// }
+/// ```
+#[proc_macro_derive(ConfigurationProperties, attributes(config, validate))]
+pub fn configuration_properties(input: TokenStream) -> TokenStream {
+    configuration_properties::with_configuration_properties(input)
+}
+
+/// Registers the async function as the listener of the events of the type it takes by reference.
+///
+/// # Attributes
+/// - `#[event_listener(name = "orderListener")]`: optional, the listener bean is named after
+///   the function by default.
+///
+/// # Examples
+/// ```ignore
+/// # use application_web_macros::event_listener;
+/// #[event_listener]
+/// async fn on_order_created(event: &OrderCreated) -> Result<(), Box<dyn Error + Send + Sync>> {
+///     info!("Order {} created", event.id);
+///     Ok(())
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn event_listener(args: TokenStream, input: TokenStream) -> TokenStream {
+    event_listener::with_event_listener(args, input)
+}
+
 fn input_and_compile_error(mut item: TokenStream, err: syn::Error) -> TokenStream {
     let compile_err = TokenStream::from(err.to_compile_error());
     item.extend(compile_err);
@@ -0,0 +1,192 @@
+use application_beans::factory::bean_factory::BeanFactory;
+use application_context::context::application_context::APPLICATION_CONTEXT;
+use application_core::env::configuration_properties::ConfigurationProperties;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use std::any::type_name;
+use std::ops::Deref;
+use std::sync::Arc;
+use tracing::error;
+
+/// Extractor resolving a bean of type `T` from the application context,
+/// responds with 500 if there is none.
+pub struct Bean<T: ?Sized>(pub Arc<T>);
+
+impl<T: ?Sized> Deref for Bean<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, S> FromRequestParts<S> for Bean<T>
+where
+    T: ?Sized + Send + Sync + 'static,
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, String);
+
+    async fn from_request_parts(_parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let application_context = APPLICATION_CONTEXT.read().await;
+        match application_context.get_bean_factory().get_bean::<T>() {
+            Some(bean) => Ok(Bean(bean)),
+            None => {
+                let message = format!("No bean of type '{}' is registered", type_name::<T>());
+                error!("{}", message);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, message))
+            }
+        }
+    }
+}
+
+/// Extractor binding the configuration `T` from the environment of the application context,
+/// responds with 500 if the properties under `T::PREFIX` are missing or invalid.
+///
+/// The configurations derived with `#[derive(ConfigurationProperties)]` are also beans,
+/// [`Bean<T>`] returns the instance bound at startup.
+pub struct Config<T>(pub T);
+
+impl<T> Deref for Config<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, S> FromRequestParts<S> for Config<T>
+where
+    T: ConfigurationProperties + Send,
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, String);
+
+    async fn from_request_parts(_parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let application_context = APPLICATION_CONTEXT.read().await;
+        let environment = application_context.get_environment().await;
+        match T::bind(&environment) {
+            Ok(config) => Ok(Config(config)),
+            Err(e) => {
+                let message = format!(
+                    "Configuration '{}' cannot be bound from the properties under '{}', {}",
+                    type_name::<T>(),
+                    T::PREFIX,
+                    e
+                );
+                error!("{}", message);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, message))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use application_beans::factory::bean_factory::ConfigurableBeanFactory;
+    use application_core::env::configuration_properties::{BindError, InvalidProperty};
+    use application_core::env::environment::{ApplicationEnvironment, ConfigurableEnvironment};
+    use application_core::env::property::PropertySource;
+    use application_core::env::property_resolver::PropertyResolver;
+    use axum::http::Request;
+
+    struct Registered(&'static str);
+
+    struct Unregistered;
+
+    /// Bound from `greeting.message`, `farewell.message` for `Farewell`.
+    struct Message<const FAREWELL: bool>(String);
+
+    type Greeting = Message<false>;
+
+    type Farewell = Message<true>;
+
+    impl<const FAREWELL: bool> ConfigurationProperties for Message<FAREWELL> {
+        const PREFIX: &'static str = if FAREWELL { "farewell" } else { "greeting" };
+
+        fn bind(environment: &ApplicationEnvironment) -> Result<Self, BindError> {
+            let key = format!("{}.message", Self::PREFIX);
+            match environment.get_property::<String>(&key) {
+                Some(message) => Ok(Message(message)),
+                None => Err(BindError {
+                    invalid_properties: vec![InvalidProperty {
+                        key,
+                        value: None,
+                        origin: None,
+                        reason: "is required but not set".to_string(),
+                    }],
+                }),
+            }
+        }
+    }
+
+    fn parts() -> Parts {
+        Request::new(()).into_parts().0
+    }
+
+    #[tokio::test]
+    async fn beans_are_extracted_from_the_application_context() {
+        let application_context = APPLICATION_CONTEXT.read().await.clone();
+        application_context
+            .get_bean_factory()
+            .set(Registered("hello"))
+            .unwrap();
+
+        let Ok(bean) = Bean::<Registered>::from_request_parts(&mut parts(), &()).await else {
+            panic!("the bean is registered");
+        };
+        assert_eq!(bean.0 .0, "hello");
+
+        let Err(rejection) = Bean::<Unregistered>::from_request_parts(&mut parts(), &()).await
+        else {
+            panic!("the bean is not registered");
+        };
+        assert_eq!(
+            rejection,
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!(
+                    "No bean of type '{}' is registered",
+                    type_name::<Unregistered>()
+                )
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn configurations_are_bound_from_the_environment() {
+        let source = config::Config::builder()
+            .set_override("greeting.message", "hello")
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut environment = ApplicationEnvironment::default();
+        environment.add_property_source(PropertySource {
+            name: "test".to_string(),
+            source,
+        });
+        let application_context = APPLICATION_CONTEXT.read().await.clone();
+        application_context.set_environment(environment).await;
+
+        let Ok(greeting) = Config::<Greeting>::from_request_parts(&mut parts(), &()).await else {
+            panic!("greeting.message is set");
+        };
+        assert_eq!(greeting.0 .0, "hello");
+
+        let Err(rejection) = Config::<Farewell>::from_request_parts(&mut parts(), &()).await else {
+            panic!("farewell.message is not set");
+        };
+        assert_eq!(rejection.0, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(
+            rejection.1,
+            format!(
+                "Configuration '{}' cannot be bound from the properties under 'farewell', \
+                 Failed to bind the configuration properties\n    farewell.message: is required \
+                 but not set",
+                type_name::<Farewell>()
+            )
+        );
+    }
+}
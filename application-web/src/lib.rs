@@ -1,6 +1,8 @@
+pub mod extract;
 pub mod handler;
 pub mod request;
 pub mod response;
+pub mod scope;
 pub mod server;
 
 pub type MethodFilter = axum::routing::MethodFilter;
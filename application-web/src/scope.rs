@@ -0,0 +1,10 @@
+use application_beans::factory::scope::RequestScope;
+use axum::extract::Request;
+use axum::middleware::Next;
+use axum::response::Response;
+
+/// Middleware handling every request in its own [`RequestScope`], request scoped
+/// beans are created on first lookup and dropped with the response.
+pub async fn request_scope(request: Request, next: Next) -> Response {
+    RequestScope::run(next.run(request)).await
+}
@@ -1,13 +1,18 @@
 use crate::handler::auto_router;
+use crate::scope::request_scope;
 use async_std::task::block_on;
 use async_trait::async_trait;
-use axum::Router;
+use axum::{middleware, Router};
 use axum_server::Handle;
 use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::ErrorKind;
 use std::net::SocketAddr;
-use std::sync::{Arc, Condvar, Mutex};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
 use std::time::Duration;
 use tokio::net::TcpListener;
+use tokio::sync::{watch, Notify};
+use tokio::task::JoinHandle;
 use tokio::{signal, spawn};
 use tracing::info;
 
@@ -15,13 +20,46 @@ use tracing::info;
 pub trait WebServer: Send + Sync {
     fn get_port(&self) -> u16;
 
-    fn start(&self, router: Router) -> Result<Arc<(Mutex<bool>, Condvar)>, Box<dyn Error>>;
+    /// Serve the routes of [`auto_router`] merged with `router`, each request handled in its
+    /// own request scope, see [`request_scope`].
+    fn start(
+        &self,
+        router: Router,
+    ) -> Result<Arc<(Mutex<bool>, Condvar)>, Box<dyn Error + Send + Sync>>;
 
+    /// Stop the server started last, once its connections are closed.
     async fn stop(&self) -> Result<(), Box<dyn Error>>;
 }
 
+/// The port the web server should listen on is already in use.
+#[derive(Debug)]
+pub struct PortInUseError {
+    pub port: u16,
+}
+
+impl Display for PortInUseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Port {} is already in use", self.port)
+    }
+}
+
+impl Error for PortInUseError {}
+
 pub struct AxumServer {
     pub port: u16,
+    /// Notified by [`WebServer::stop`], the server shuts down as on the termination signal.
+    shutdown: Arc<Notify>,
+    server: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl AxumServer {
+    pub fn new(port: u16) -> Self {
+        AxumServer {
+            port,
+            shutdown: Arc::new(Notify::new()),
+            server: Mutex::new(None),
+        }
+    }
 }
 
 #[async_trait]
@@ -30,31 +68,56 @@ impl WebServer for AxumServer {
         self.port
     }
 
-    fn start(&self, router: Router) -> Result<Arc<(Mutex<bool>, Condvar)>, Box<dyn Error>> {
+    fn start(
+        &self,
+        router: Router,
+    ) -> Result<Arc<(Mutex<bool>, Condvar)>, Box<dyn Error + Send + Sync>> {
         let mut app = auto_router();
-        app = app.merge(router);
+        app = app.merge(router).layer(middleware::from_fn(request_scope));
 
         // run it with hyper on localhost
         let addr = SocketAddr::from(([0, 0, 0, 0], self.port));
-        let tcp_listener = block_on(TcpListener::bind(addr))?;
+        let tcp_listener =
+            block_on(TcpListener::bind(addr)).map_err(|e| -> Box<dyn Error + Send + Sync> {
+                match e.kind() {
+                    ErrorKind::AddrInUse => Box::new(PortInUseError { port: self.port }),
+                    _ => Box::new(e),
+                }
+            })?;
         info!("Start axum server, listening on {}", addr);
 
         let pair = Arc::new((Mutex::new(false), Condvar::new()));
-        spawn(Self::run(app, tcp_listener, Arc::clone(&pair)));
+        let server = spawn(Self::run(
+            app,
+            tcp_listener,
+            Arc::clone(&pair),
+            self.shutdown.clone(),
+        ));
+        *self.server.lock().unwrap() = Some(server);
         Ok(pair)
     }
 
     async fn stop(&self) -> Result<(), Box<dyn Error>> {
+        let server = self.server.lock().unwrap().take();
+        if let Some(server) = server {
+            self.shutdown.notify_one();
+            server.await?;
+        }
         Ok(())
     }
 }
 
 impl AxumServer {
-    async fn run(app: Router, listener: TcpListener, condvar_pair: Arc<(Mutex<bool>, Condvar)>) {
+    async fn run(
+        app: Router,
+        listener: TcpListener,
+        condvar_pair: Arc<(Mutex<bool>, Condvar)>,
+        shutdown: Arc<Notify>,
+    ) {
         //Create a handle for our TLS server so the shutdown signal can all shutdown
         let handle = Handle::new();
         //save the future for easy shutting down of redirect server
-        let shutdown_future = shutdown_signal(handle.clone(), condvar_pair);
+        let shutdown_future = shutdown_signal(handle.clone(), condvar_pair, shutdown);
         // Run the server with graceful shutdown
         axum::serve(listener, app)
             .with_graceful_shutdown(shutdown_future)
@@ -63,7 +126,55 @@ impl AxumServer {
     }
 }
 
-async fn shutdown_signal(handle: Handle<SocketAddr>, condvar_pair: Arc<(Mutex<bool>, Condvar)>) {
+async fn shutdown_signal(
+    handle: Handle<SocketAddr>,
+    condvar_pair: Arc<(Mutex<bool>, Condvar)>,
+    shutdown: Arc<Notify>,
+) {
+    tokio::select! {
+        _ = termination_signal() => info!("Received termination signal shutting down"),
+        _ = shutdown.notified() => info!("Stopping axum server"),
+    }
+    handle.graceful_shutdown(Some(Duration::from_secs(10)));
+    let (lock, cvar) = &*condvar_pair;
+    let mut stopped = lock.lock().unwrap();
+    *stopped = true;
+    cvar.notify_one();
+}
+
+static TERMINATION: OnceLock<watch::Receiver<bool>> = OnceLock::new();
+
+/// Resolves once the process receives Ctrl-C or SIGTERM, the signal handlers are installed on
+/// the first call. Every caller is woken by the same signal.
+pub async fn termination_signal() {
+    let mut termination = TERMINATION
+        .get_or_init(|| {
+            let (sender, receiver) = watch::channel(false);
+            spawn(async move {
+                wait_for_signal().await;
+                let _ = sender.send(true);
+            });
+            receiver
+        })
+        .clone();
+    // the runtime listening for the signal is gone
+    if termination
+        .wait_for(|terminated| *terminated)
+        .await
+        .is_err()
+    {
+        std::future::pending::<()>().await;
+    }
+}
+
+/// Whether the process received Ctrl-C or SIGTERM since [`termination_signal`] was first called.
+pub fn is_terminating() -> bool {
+    TERMINATION
+        .get()
+        .is_some_and(|termination| *termination.borrow())
+}
+
+async fn wait_for_signal() {
     let ctrl_c = async {
         signal::ctrl_c()
             .await
@@ -85,10 +196,4 @@ async fn shutdown_signal(handle: Handle<SocketAddr>, condvar_pair: Arc<(Mutex<bo
         _ = ctrl_c => {},
         _ = terminate => {},
     }
-    info!("Received termination signal shutting down");
-    handle.graceful_shutdown(Some(Duration::from_secs(10)));
-    let (lock, cvar) = &*condvar_pair;
-    let mut stopped = lock.lock().unwrap();
-    *stopped = true;
-    cvar.notify_one();
 }
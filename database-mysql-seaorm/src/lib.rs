@@ -1,5 +1,11 @@
+use application_beans::factory::bean_error::BeanError;
+use application_beans::factory::bean_factory::ConfigurableBeanFactory;
+use application_beans::factory::disposable_bean::DisposableBean;
+use async_trait::async_trait;
 use database_common::connection::DbConnection;
 use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+use std::error::Error;
+use std::sync::Arc;
 use std::time::Duration;
 use tracing::log;
 
@@ -8,6 +14,8 @@ pub struct Dao {
 }
 
 impl Dao {
+    pub const BEAN_NAME: &'static str = "dao";
+
     pub async fn new(connection: DbConnection) -> Dao {
         let db_url = connection.to_string();
         let mut opt = ConnectOptions::new(db_url);
@@ -25,4 +33,23 @@ impl Dao {
             .expect("Could not connect to database");
         Dao { connection: db }
     }
+
+    /// Register the dao as a bean whose connections are closed with the context.
+    pub fn register(
+        self,
+        bean_factory: &impl ConfigurableBeanFactory,
+    ) -> Result<Arc<Dao>, BeanError> {
+        let dao = Arc::new(self);
+        bean_factory.set_as(Self::BEAN_NAME, dao.clone())?;
+        bean_factory.set_as::<dyn DisposableBean>(Self::BEAN_NAME, dao.clone())?;
+        Ok(dao)
+    }
+}
+
+#[async_trait]
+impl DisposableBean for Dao {
+    async fn destroy(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.connection.clone().close().await?;
+        Ok(())
+    }
 }